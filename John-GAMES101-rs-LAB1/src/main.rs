@@ -1,30 +1,46 @@
-mod triangle;
-mod rasterizer;
-mod utils;
+#[cfg(feature = "opencv")]
 extern crate opencv;
 use std::env;
+use std::process;
 use nalgebra::{Vector3};
-use opencv::core::Vector;
+#[cfg(feature = "opencv")]
 use opencv::highgui::{imshow, wait_key};
-use opencv::imgcodecs::imwrite;
-use crate::rasterizer::{Primitive, Rasterizer};
-use utils::*;
-use std::thread;
+use games101::camera::Projection;
+use games101::export::save_image;
+use games101::rasterizer::{self, Primitive, Rasterizer};
+#[cfg(feature = "opencv")]
+use games101::rasterizer::{IndBufId, PosBufId};
+use games101::utils::*;
+
+const USAGE: &str = "usage: lab1 [angle [filename [width height]]]";
+
+fn usage(error: &str) -> ! {
+    eprintln!("{}\n{}", error, USAGE);
+    process::exit(2)
+}
 
 fn main() {
     let mut angle = 0.0;
     let mut command_line = false;
     let mut filename = "output.png";
+    let (mut width, mut height) = (700, 700);
     let argv: Vec<String> = env::args().collect();
-    if argv.len() >= 2 {
-        command_line = true;
-        angle = argv[1].parse().unwrap();
-        if argv.len() == 3 {
-            filename = &argv[2];
+    match argv.len() {
+        1 => {}
+        2 | 3 | 5 => {
+            command_line = true;
+            angle = argv[1].parse().unwrap_or_else(|_| usage(&format!("invalid angle `{}`", argv[1])));
+            if argv.len() >= 3 {
+                filename = &argv[2];
+            }
+            if argv.len() == 5 {
+                (width, height) = parse_size(&argv[3], &argv[4]).unwrap_or_else(|e| usage(&e));
+            }
         }
+        _ => usage("width and height go together"),
     }
 
-    let mut r = Rasterizer::new(700, 700);
+    let mut r = Rasterizer::new(width, height);
     let eye_pos = Vector3::new(0.0, 0.0, 5.0);
     let pos = vec![Vector3::new(2.0, 0.0, -2.0),
                    Vector3::new(0.0, 2.0, -2.0),
@@ -34,34 +50,39 @@ fn main() {
     let pos_id = r.load_position(&pos);
     let ind_id = r.load_indices(&ind);
 
-    let mut k = 0;
-    let mut frame_count = 0;
-    let mut angle_rotation = 0.0;
-
-    // let mut x1,x2,x3;
-    if command_line {
+    // without opencv there is no window, so always render to a file
+    if command_line || cfg!(not(feature = "opencv")) {
         r.clear(rasterizer::Buffer::Both);
         r.set_model(get_model_matrix(angle));
         r.set_view(get_view_matrix(eye_pos));
-        r.set_projection(get_projection_matrix(45.0, 1.0, 0.1, 50.0));
-        r.draw_triangle(pos_id, ind_id, Primitive::Triangle);
+        r.set_projection_params(Projection::new(45.0, r.aspect_ratio(), 0.1, 50.0));
+        r.draw_triangle(pos_id, ind_id, Primitive::Line);
+        r.resolve();
 
-        let frame_buffer = r.frame_buffer();
-        let image = frame_buffer2cv_mat(frame_buffer);
-
-        imwrite(filename, &image, &Vector::default()).unwrap();
-        return;
+        save_image(filename, r.frame_buffer(), r.width(), r.height()).unwrap();
+    } else {
+        #[cfg(feature = "opencv")]
+        show(&mut r, pos_id, ind_id, eye_pos, angle);
     }
+}
+
+#[cfg(feature = "opencv")]
+fn show(r: &mut Rasterizer, pos_id: PosBufId, ind_id: IndBufId, eye_pos: V3d, mut angle: f64) {
+    let mut k = 0;
+    let mut frame_count = 0;
+    let mut angle_rotation = 0.0;
+
     while k != 27 {
         r.clear(rasterizer::Buffer::Both);
         r.set_model(get_model_matrix(angle));
         r.set_view(get_view_matrix(eye_pos));
-        r.set_projection(get_projection_matrix(45.0, 1.0, 0.1, 50.0));
+        r.set_projection_params(Projection::new(45.0, r.aspect_ratio(), 0.1, 50.0));
         r.set_rotation(get_rotation(V3d::new(1.0,2.0, 3.0), angle_rotation));
-        r.draw_triangle(pos_id, ind_id, Primitive::Triangle);
+        r.draw_triangle(pos_id, ind_id, Primitive::Line);
+        r.resolve();
 
         let frame_buffer = r.frame_buffer();
-        let image = frame_buffer2cv_mat(frame_buffer);
+        let image = frame_buffer2cv_mat(frame_buffer, r.width(), r.height());
         imshow("image", &image).unwrap();
 
         k = wait_key(80).unwrap();
@@ -76,4 +97,4 @@ fn main() {
           }
         frame_count += 1;
     }
-}
\ No newline at end of file
+}
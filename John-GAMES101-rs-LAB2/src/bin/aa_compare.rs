@@ -0,0 +1,59 @@
+use std::env;
+use std::process;
+use games101::compare::{compose, find_edge_crop, Panel};
+use games101::export::frame_buffer2image;
+use games101::fxaa::FxaaSettings;
+use games101::msaa::{ResolveFilter, SamplePattern};
+use games101::rasterizer::{AntiAliasing, Rasterizer};
+use games101::taa::TaaSettings;
+use games101::utils::parse_size;
+use lab2::Scene;
+
+// frames TAA gets to converge on the still scene
+const TAA_FRAMES: usize = 16;
+
+const USAGE: &str = "usage: aa_compare [filename [width height]]";
+
+fn usage(error: &str) -> ! {
+    eprintln!("{}\n{}", error, USAGE);
+    process::exit(2)
+}
+
+// renders the LAB2 scene once per antialiasing mode into one labelled PNG
+fn main() {
+    let argv: Vec<String> = env::args().collect();
+    let filename = argv.get(1).map(String::as_str).unwrap_or("aa_compare.png");
+    let (width, height) = match argv.len() {
+        1 | 2 => (400, 400),
+        4 => parse_size(&argv[2], &argv[3]).unwrap_or_else(|e| usage(&e)),
+        _ => usage("width and height go together"),
+    };
+
+    let modes = [
+        ("NONE", AntiAliasing::None),
+        ("SSAA 4X", AntiAliasing::Ssaa { factor: 4, filter: ResolveFilter::Box }),
+        ("MSAA 4X RGSS", AntiAliasing::Msaa { samples: 4, pattern: SamplePattern::RotatedGrid, filter: ResolveFilter::Box }),
+        ("FXAA", AntiAliasing::Fxaa(FxaaSettings::default())),
+        ("TAA", AntiAliasing::Taa(TaaSettings::default())),
+    ];
+    let panels: Vec<Panel> = modes.into_iter().map(|(label, mode)| {
+        let frames = if matches!(mode, AntiAliasing::Taa(_)) { TAA_FRAMES } else { 1 };
+        let r = render(width, height, mode, frames);
+        Panel { label: label.to_string(), image: frame_buffer2image(r.frame_buffer(), r.width(), r.height()) }
+    }).collect();
+
+    // zoom in where the aliased and the supersampled frames differ most
+    let size = (width.min(height) / 8) as u32;
+    let crop = find_edge_crop(&panels[0].image, &panels[1].image, size);
+    compose(&panels, crop, 4, panels.len()).save(filename).unwrap();
+}
+
+fn render(width: u64, height: u64, mode: AntiAliasing, frames: usize) -> Rasterizer {
+    let mut r = Rasterizer::new(width, height);
+    r.set_antialiasing(mode);
+    let scene = Scene::load(&mut r);
+    for _ in 0..frames {
+        scene.render(&mut r);
+    }
+    r
+}
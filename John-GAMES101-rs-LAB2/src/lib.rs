@@ -0,0 +1,50 @@
+use nalgebra::Vector3;
+use games101::camera::Projection;
+use games101::rasterizer::{Buffer, ColBufId, IndBufId, PosBufId, Primitive, Rasterizer};
+use games101::utils::*;
+
+// the three overlapping triangles of the assignment, shared by lab2 and aa_compare
+pub struct Scene {
+    pos_id: PosBufId,
+    ind_id: IndBufId,
+    col_id: ColBufId,
+}
+
+impl Scene {
+    pub fn load(r: &mut Rasterizer) -> Self {
+        let pos = vec![Vector3::new(2.0, 0.0, -2.0),
+                       Vector3::new(0.0, 2.0, -2.0),
+                       Vector3::new(-2.0, 0.0, -2.0),
+                       Vector3::new(3.5, -1.0, -5.0),
+                       Vector3::new(2.5, 1.5, -5.0),
+                       Vector3::new(-1.0, 0.5, -5.0),
+                       Vector3::new(-3.5, -3.5, -6.0),
+                       Vector3::new(3.5, 1.5, -6.0),
+                       Vector3::new(-2.0, 2.5, -6.0)];
+        let ind = vec![Vector3::new(0, 1, 2), Vector3::new(3, 4, 5), Vector3::new(6, 7, 8)];
+        let cols = vec![Vector3::new(217.0, 238.0, 185.0),
+                        Vector3::new(217.0, 238.0, 185.0),
+                        Vector3::new(217.0, 238.0, 185.0),
+                        Vector3::new(185.0, 217.0, 238.0),
+                        Vector3::new(185.0, 217.0, 238.0),
+                        Vector3::new(185.0, 217.0, 238.0),
+                        Vector3::new(238.0, 185.0, 217.0),
+                        Vector3::new(238.0, 185.0, 217.0),
+                        Vector3::new(238.0, 185.0, 217.0)];
+        Scene {
+            pos_id: r.load_position(&pos),
+            ind_id: r.load_indices(&ind),
+            col_id: r.load_colors(&cols),
+        }
+    }
+
+    // one frame from (0, 0, 5), the frame buffer holds it afterwards
+    pub fn render(&self, r: &mut Rasterizer) {
+        r.clear(Buffer::Both);
+        r.set_model(get_model_matrix(0.0));
+        r.set_view(get_view_matrix(Vector3::new(0.0, 0.0, 5.0)));
+        r.set_projection_params(Projection::new(45.0, r.aspect_ratio(), 0.1, 50.0));
+        r.draw(self.pos_id, self.ind_id, self.col_id, Primitive::Triangle);
+        r.resolve();
+    }
+}
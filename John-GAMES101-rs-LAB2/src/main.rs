@@ -1,63 +1,72 @@
-mod triangle;
-mod rasterizer;
-mod utils;
-
+#[cfg(feature = "opencv")]
 extern crate opencv;
 
-use nalgebra::{Vector3};
+use std::env;
+use std::process;
+#[cfg(feature = "opencv")]
 use opencv::{
     Result,
 };
+#[cfg(feature = "opencv")]
 use opencv::highgui::{imshow, wait_key};
-use crate::rasterizer::{Primitive, Rasterizer};
-use utils::*;
-
-fn main() -> Result<()> {
-    let mut r = Rasterizer::new(700, 700);
-    let eye_pos = Vector3::new(0.0, 0.0, 5.0);
-    let pos = vec![Vector3::new(2.0, 0.0, -2.0),
-                   Vector3::new(0.0, 2.0, -2.0),
-                   Vector3::new(-2.0, 0.0, -2.0),
-                   Vector3::new(3.5, -1.0, -5.0),
-                   Vector3::new(2.5, 1.5, -5.0),
-                   Vector3::new(-1.0, 0.5, -5.0),
-                   Vector3::new(-3.5, -3.5, -6.0),
-                   Vector3::new(3.5, 1.5, -6.0),
-                   Vector3::new(-2.0, 2.5, -6.0)];
-    let ind = vec![Vector3::new(0, 1, 2), Vector3::new(3, 4, 5), Vector3::new(6, 7, 8)];
-    let cols = vec![Vector3::new(217.0, 238.0, 185.0),
-                    Vector3::new(217.0, 238.0, 185.0),
-                    Vector3::new(217.0, 238.0, 185.0),
-                    Vector3::new(185.0, 217.0, 238.0),
-                    Vector3::new(185.0, 217.0, 238.0),
-                    Vector3::new(185.0, 217.0, 238.0),
-                    Vector3::new(238.0, 185.0, 217.0),
-                    Vector3::new(238.0, 185.0, 217.0),
-                    Vector3::new(238.0, 185.0, 217.0)];
-    let pos_id = r.load_position(&pos);
-    let ind_id = r.load_indices(&ind);
-    let col_id = r.load_colors(&cols);
+use games101::export::save_image;
+use games101::fxaa::FxaaSettings;
+use games101::rasterizer::{AntiAliasing, Rasterizer};
+#[cfg(feature = "opencv")]
+use games101::taa::TaaSettings;
+#[cfg(feature = "opencv")]
+use games101::utils::frame_buffer2cv_mat;
+use games101::utils::parse_size;
+use lab2::Scene;
+
+const USAGE: &str = "usage: lab2 [filename [width height]]";
+
+fn usage(error: &str) -> ! {
+    eprintln!("{}\n{}", error, USAGE);
+    process::exit(2)
+}
+
+fn main() {
+    let argv: Vec<String> = env::args().collect();
+    let (width, height) = match argv.len() {
+        1 | 2 => (700, 700),
+        4 => parse_size(&argv[2], &argv[3]).unwrap_or_else(|e| usage(&e)),
+        _ => usage("width and height go together"),
+    };
+    let mut r = Rasterizer::new(width, height);
+    // a single frame gets FXAA alone, set_antialiasing turns off the default MSAA
+    r.set_antialiasing(AntiAliasing::Fxaa(FxaaSettings::default()));
+    let scene = Scene::load(&mut r);
+
+    // `lab2 filename` renders a single frame to a file, as does a build without opencv
+    if argv.len() >= 2 || cfg!(not(feature = "opencv")) {
+        let filename = argv.get(1).map(String::as_str).unwrap_or("output.png");
+        scene.render(&mut r);
+        save_image(filename, r.frame_buffer(), r.width(), r.height()).unwrap();
+    } else {
+        #[cfg(feature = "opencv")]
+        show(&mut r, &scene).unwrap();
+    }
+}
+
+#[cfg(feature = "opencv")]
+fn show(r: &mut Rasterizer, scene: &Scene) -> Result<()> {
     let mut k = 0;
     let mut frame_count = 0;
+    // the window shows the same scene frame after frame, so TAA converges on it and replaces FXAA
+    r.set_antialiasing(AntiAliasing::Taa(TaaSettings::default()));
 
     while k != 27 {
-        r.clear(rasterizer::Buffer::Both);
-        r.set_model(get_model_matrix(0.0));
-        r.set_view(get_view_matrix(eye_pos));
-        r.set_projection(get_projection_matrix(45.0, 1.0, 0.1, 50.0));
-        //r.set_jitter(get_jitter(45.0, 1.0, 0.1, 50.0));
-        //if frame_count >= 1 {
-        r.draw(pos_id, ind_id, col_id, Primitive::Triangle);
-        //}
+        scene.render(r);
 
         let frame_buffer = r.frame_buffer();
-        let image = frame_buffer2cv_mat(frame_buffer);
+        let image = frame_buffer2cv_mat(frame_buffer, r.width(), r.height());
 
         imshow("image", &image)?;
-        k = wait_key(2000).unwrap();
+        k = wait_key(80).unwrap();
         println!("frame count: {}", frame_count);
         frame_count += 1;
     };
 
     Ok(())
-}
\ No newline at end of file
+}
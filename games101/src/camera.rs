@@ -0,0 +1,255 @@
+use std::f64::consts::PI;
+use nalgebra::{Matrix4, Rotation3, Unit};
+use crate::utils::{get_frustum_matrix, get_look_at_matrix, get_orthographic_matrix, get_projection_matrix, V3d};
+
+// how the view space is flattened onto the screen
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ProjectionMode {
+    // the field of view and aspect ratio of Projection
+    #[default]
+    Perspective,
+    // parallel rays, height is how much of the world fits on the screen from bottom to top
+    Orthographic { height: f64 },
+    // parallel rays with depth drawn along a line at angle degrees from the screen x axis,
+    // shortened by depth_scale. the plane at distance focus in front of the camera stays in place
+    Oblique { height: f64, angle: f64, depth_scale: f64, focus: f64 },
+    // perspective through a window that need not be centered, its edges at distance 1 in front
+    // of the camera
+    OffAxis { left: f64, right: f64, bottom: f64, top: f64 },
+}
+
+impl ProjectionMode {
+    // depth at full length
+    pub fn cavalier(height: f64, focus: f64) -> Self {
+        ProjectionMode::Oblique { height, angle: 45.0, depth_scale: 1.0, focus }
+    }
+
+    // depth at half length, which looks closer to its real size
+    pub fn cabinet(height: f64, focus: f64) -> Self {
+        ProjectionMode::Oblique { height, angle: 45.0, depth_scale: 0.5, focus }
+    }
+
+    // one eye of a stereo pair: the eye sits eye_offset along the camera's right axis (negative
+    // for the left eye) and looks straight ahead, both windows meet at distance convergence
+    pub fn stereo(eye_fov: f64, aspect_ratio: f64, eye_offset: f64, convergence: f64) -> Self {
+        let top = (eye_fov / 2.0).to_radians().tan();
+        let shift = eye_offset / convergence;
+        ProjectionMode::OffAxis { left: -top * aspect_ratio - shift, right: top * aspect_ratio - shift, bottom: -top, top }
+    }
+}
+
+// what the projection matrix was built from, kept so the viewport can use the same near and far
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projection {
+    pub eye_fov: f64,
+    pub aspect_ratio: f64,
+    pub z_near: f64,
+    pub z_far: f64,
+    pub mode: ProjectionMode,
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Projection::new(45.0, 1.0, 0.1, 50.0)
+    }
+}
+
+impl Projection {
+    // perspective
+    pub fn new(eye_fov: f64, aspect_ratio: f64, z_near: f64, z_far: f64) -> Self {
+        Projection { eye_fov, aspect_ratio, z_near, z_far, mode: ProjectionMode::Perspective }
+    }
+
+    pub fn with_mode(self, mode: ProjectionMode) -> Self {
+        Projection { mode, ..self }
+    }
+
+    pub fn matrix(&self) -> Matrix4<f64> {
+        let (n, f) = (self.z_near, self.z_far);
+        match self.mode {
+            ProjectionMode::Perspective => get_projection_matrix(self.eye_fov, self.aspect_ratio, n, f),
+            ProjectionMode::Orthographic { height } => {
+                let (t, r) = (height / 2.0, height / 2.0 * self.aspect_ratio);
+                get_orthographic_matrix(-r, r, -t, t, n, f)
+            }
+            ProjectionMode::Oblique { height, angle, depth_scale, focus } => {
+                let (t, r) = (height / 2.0, height / 2.0 * self.aspect_ratio);
+                // x and y move by the distance past the focus plane, which is -z - focus
+                let (dx, dy) = (depth_scale * angle.to_radians().cos(), depth_scale * angle.to_radians().sin());
+                let shear = Matrix4::new(
+                    1.0, 0.0, -dx, -dx * focus,
+                    0.0, 1.0, -dy, -dy * focus,
+                    0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                );
+                get_orthographic_matrix(-r, r, -t, t, n, f) * shear
+            }
+            ProjectionMode::OffAxis { left, right, bottom, top } =>
+                get_frustum_matrix(left * n, right * n, bottom * n, top * n, n, f),
+        }
+    }
+
+    // the depth range that stores view distance, [z_near, z_far]
+    pub fn depth_range(&self) -> DepthRange {
+        DepthRange::new(self.z_near, self.z_far)
+    }
+}
+
+// a camera looking from position at target, fov is vertical and in degrees
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    pub position: V3d,
+    pub target: V3d,
+    pub up: V3d,
+    pub fov: f64,
+    pub aspect_ratio: f64,
+    pub z_near: f64,
+    pub z_far: f64,
+    // fov only matters for Perspective
+    pub mode: ProjectionMode,
+}
+
+impl Default for Camera {
+    // where the assignments put the eye
+    fn default() -> Self {
+        Camera::new(V3d::new(0.0, 0.0, 5.0), V3d::zeros())
+    }
+}
+
+// how close orbit and look get to straight up or down, past it the view would flip
+const MIN_POLAR: f64 = 1e-3;
+
+// v turned counter clockwise about up by yaw and toward up by pitch, both in degrees
+fn turn(v: V3d, up: V3d, yaw: f64, pitch: f64) -> V3d {
+    let up = Unit::new_normalize(up);
+    let v = Rotation3::from_axis_angle(&up, yaw.to_radians()) * v;
+    let polar = (v.normalize().dot(&up)).clamp(-1.0, 1.0).acos();
+    let target = (polar - pitch.to_radians()).clamp(MIN_POLAR, PI - MIN_POLAR);
+    match Unit::try_new(v.cross(&up), 1e-12) {
+        Some(axis) => Rotation3::from_axis_angle(&axis, polar - target) * v,
+        None => v,
+    }
+}
+
+impl Camera {
+    pub fn new(position: V3d, target: V3d) -> Self {
+        let p = Projection::default();
+        Camera {
+            position,
+            target,
+            up: V3d::new(0.0, 1.0, 0.0),
+            fov: p.eye_fov,
+            aspect_ratio: p.aspect_ratio,
+            z_near: p.z_near,
+            z_far: p.z_far,
+            mode: p.mode,
+        }
+    }
+
+    pub fn view_matrix(&self) -> Matrix4<f64> {
+        get_look_at_matrix(self.position, self.target, self.up)
+    }
+
+    pub fn projection(&self) -> Projection {
+        Projection::new(self.fov, self.aspect_ratio, self.z_near, self.z_far).with_mode(self.mode)
+    }
+
+    pub fn projection_matrix(&self) -> Matrix4<f64> {
+        self.projection().matrix()
+    }
+
+    pub fn forward(&self) -> V3d {
+        (self.target - self.position).normalize()
+    }
+
+    pub fn right(&self) -> V3d {
+        self.forward().cross(&self.up).normalize()
+    }
+
+    // up made perpendicular to the view direction, what is up on screen
+    pub fn screen_up(&self) -> V3d {
+        self.right().cross(&self.forward())
+    }
+
+    // swings the camera around the target, yaw about up and pitch over the top, in degrees
+    pub fn orbit(&mut self, yaw: f64, pitch: f64) {
+        self.position = self.target + turn(self.position - self.target, self.up, yaw, pitch);
+    }
+
+    // slides camera and target along the screen, in world units
+    pub fn pan(&mut self, right: f64, up: f64) {
+        let offset = self.right() * right + self.screen_up() * up;
+        self.position += offset;
+        self.target += offset;
+    }
+
+    // moves toward the target, but not closer than z_near
+    pub fn dolly(&mut self, distance: f64) {
+        let offset = self.target - self.position;
+        let length = (offset.norm() - distance).max(self.z_near);
+        self.position = self.target - offset.normalize() * length;
+    }
+
+    // turns the view direction in place like a first person camera, positive pitch looks up
+    pub fn look(&mut self, yaw: f64, pitch: f64) {
+        self.target = self.position + turn(self.target - self.position, self.up, yaw, pitch);
+    }
+
+    // first person movement, forward and right stay level with the ground and up is along up
+    pub fn walk(&mut self, forward: f64, right: f64, up: f64) {
+        let world_up = self.up.normalize();
+        let level = (self.forward() - world_up * self.forward().dot(&world_up)).try_normalize(1e-12)
+            .unwrap_or_else(|| self.screen_up());
+        let offset = level * forward + self.right() * right + world_up * up;
+        self.position += offset;
+        self.target += offset;
+    }
+}
+
+// where the viewport puts NDC z = -1 (near) and z = 1 (far)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthRange {
+    pub near: f64,
+    pub far: f64,
+}
+
+impl DepthRange {
+    pub fn new(near: f64, far: f64) -> Self {
+        DepthRange { near, far }
+    }
+
+    // the D3D / Vulkan style [0, 1]
+    pub fn unit() -> Self {
+        DepthRange::new(0.0, 1.0)
+    }
+
+    // near at 1 and far at 0, better use of float precision far away
+    pub fn reverse_z() -> Self {
+        DepthRange::new(1.0, 0.0)
+    }
+
+    // with a reversed range larger depth means closer
+    pub fn is_reversed(&self) -> bool {
+        self.near > self.far
+    }
+
+    pub fn map(&self, ndc_z: f64) -> f64 {
+        let f1 = (self.far - self.near) / 2.0;
+        let f2 = (self.far + self.near) / 2.0;
+        ndc_z * f1 + f2
+    }
+
+    // back from a stored depth to NDC z
+    pub fn unmap(&self, depth: f64) -> f64 {
+        (2.0 * depth - self.far - self.near) / (self.far - self.near)
+    }
+
+    pub fn closer(&self, a: f64, b: f64) -> bool {
+        if self.is_reversed() { a > b } else { a < b }
+    }
+
+    // the value the depth buffers are cleared to, nothing is farther
+    pub fn clear_value(&self) -> f64 {
+        if self.is_reversed() { f64::NEG_INFINITY } else { f64::INFINITY }
+    }
+}
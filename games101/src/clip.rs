@@ -0,0 +1,148 @@
+use nalgebra::{Vector2, Vector3, Vector4};
+use crate::triangle::Triangle;
+
+// a vertex in homogeneous clip space whose attributes get re-interpolated where an edge is cut,
+// clip space is linear before the divide, so plain lerp is the right thing for them
+pub trait Clip: Copy {
+    fn position(&self) -> Vector4<f64>;
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+
+// the attributes of the fixed function pipeline, normals and texture coordinates only go
+// through the shaded draws, see VertexOutput
+#[derive(Clone, Copy, Debug)]
+pub struct ClipVertex {
+    pub pos: Vector4<f64>,
+    pub color: Vector3<f64>,
+}
+
+impl ClipVertex {
+    // the ind-th vertex of a triangle whose v[] is still in clip space
+    pub fn from_triangle(t: &Triangle, ind: usize) -> Self {
+        ClipVertex {
+            pos: t.v[ind],
+            color: t.color[ind],
+        }
+    }
+}
+
+impl Clip for ClipVertex {
+    fn position(&self) -> Vector4<f64> {
+        self.pos
+    }
+
+    fn lerp(&self, other: &ClipVertex, t: f64) -> ClipVertex {
+        ClipVertex {
+            pos: self.pos + (other.pos - self.pos) * t,
+            color: self.color + (other.color - self.color) * t,
+        }
+    }
+}
+
+// a plane p keeps the points with p.dot(v) >= 0, these six are -w <= x, y, z <= w
+pub fn frustum_planes() -> [Vector4<f64>; 6] {
+    [
+        Vector4::new(1.0, 0.0, 0.0, 1.0),  // left
+        Vector4::new(-1.0, 0.0, 0.0, 1.0), // right
+        Vector4::new(0.0, 1.0, 0.0, 1.0),  // bottom
+        Vector4::new(0.0, -1.0, 0.0, 1.0), // top
+        Vector4::new(0.0, 0.0, 1.0, 1.0),  // near
+        Vector4::new(0.0, 0.0, -1.0, 1.0), // far
+    ]
+}
+
+// Sutherland-Hodgman, the polygon is cut by one plane after the other
+pub fn clip_polygon<V: Clip>(mut polygon: Vec<V>, planes: &[Vector4<f64>]) -> Vec<V> {
+    for plane in planes {
+        if polygon.is_empty() {
+            break;
+        }
+        let mut out = Vec::with_capacity(polygon.len() + 1);
+        for i in 0..polygon.len() {
+            let a = &polygon[i];
+            let b = &polygon[(i + 1) % polygon.len()];
+            let da = plane.dot(&a.position());
+            let db = plane.dot(&b.position());
+            if da >= 0.0 {
+                out.push(*a);
+            }
+            if (da >= 0.0) != (db >= 0.0) {
+                out.push(a.lerp(b, da / (da - db)));
+            }
+        }
+        polygon = out;
+    }
+    polygon
+}
+
+// parametric (Liang-Barsky style) clipping of a segment, None if nothing is left
+pub fn clip_line<V: Clip>(a: &V, b: &V, planes: &[Vector4<f64>]) -> Option<(V, V)> {
+    let (mut t0, mut t1): (f64, f64) = (0.0, 1.0);
+    for plane in planes {
+        let da = plane.dot(&a.position());
+        let db = plane.dot(&b.position());
+        if da < 0.0 && db < 0.0 {
+            return None;
+        }
+        if da < 0.0 {
+            t0 = t0.max(da / (da - db));
+        } else if db < 0.0 {
+            t1 = t1.min(da / (da - db));
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    Some((a.lerp(b, t0), a.lerp(b, t1)))
+}
+
+// Cohen-Sutherland against the screen rectangle [lo, hi], returns where along a-b the part
+// inside starts and ends so depth and color can be cut with it
+pub fn clip_line_to_rect(a: Vector2<f64>, b: Vector2<f64>, lo: Vector2<f64>, hi: Vector2<f64>) -> Option<(f64, f64)> {
+    const LEFT: u8 = 1;
+    const RIGHT: u8 = 2;
+    const BOTTOM: u8 = 4;
+    const TOP: u8 = 8;
+    let outcode = |p: Vector2<f64>| {
+        let mut code = 0;
+        if p.x < lo.x { code |= LEFT } else if p.x > hi.x { code |= RIGHT }
+        if p.y < lo.y { code |= BOTTOM } else if p.y > hi.y { code |= TOP }
+        code
+    };
+
+    let d = b - a;
+    let (mut t0, mut t1) = (0.0, 1.0);
+    let (mut code0, mut code1) = (outcode(a), outcode(b));
+    loop {
+        if code0 | code1 == 0 {
+            return Some((t0, t1));
+        }
+        if code0 & code1 != 0 {
+            return None;
+        }
+        // move the end that is outside onto the edge it is outside of
+        let first = code0 != 0;
+        let code = if first { code0 } else { code1 };
+        let t = if code & LEFT != 0 {
+            (lo.x - a.x) / d.x
+        } else if code & RIGHT != 0 {
+            (hi.x - a.x) / d.x
+        } else if code & BOTTOM != 0 {
+            (lo.y - a.y) / d.y
+        } else {
+            (hi.y - a.y) / d.y
+        };
+        // snap the coordinate that was cut so rounding cannot leave the point just outside
+        let mut p = a + d * t;
+        if code & LEFT != 0 { p.x = lo.x } else if code & RIGHT != 0 { p.x = hi.x }
+        if code & BOTTOM != 0 && code & (LEFT | RIGHT) == 0 { p.y = lo.y }
+        if code & TOP != 0 && code & (LEFT | RIGHT) == 0 { p.y = hi.y }
+        if first {
+            t0 = t;
+            code0 = outcode(p);
+        } else {
+            t1 = t;
+            code1 = outcode(p);
+        }
+    }
+}
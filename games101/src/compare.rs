@@ -0,0 +1,178 @@
+use image::{Rgb, RgbImage};
+
+// one rendering in the comparison grid
+pub struct Panel {
+    pub label: String,
+    pub image: RgbImage,
+}
+
+// a square region of the panels, in image pixels from the top left
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crop {
+    pub x: u32,
+    pub y: u32,
+    pub size: u32,
+}
+
+const BACKGROUND: Rgb<u8> = Rgb([32, 32, 32]);
+const TEXT: Rgb<u8> = Rgb([255, 255, 255]);
+const OUTLINE: Rgb<u8> = Rgb([255, 220, 0]);
+const MARGIN: u32 = 8;
+const TEXT_SCALE: u32 = 2;
+
+// the crop where the two images differ the most, e.g. an aliased and a supersampled render
+pub fn find_edge_crop(a: &RgbImage, b: &RgbImage, size: u32) -> Crop {
+    assert_eq!(a.dimensions(), b.dimensions(), "images to compare differ in size");
+    let (w, h) = a.dimensions();
+    let size = size.min(w).min(h);
+
+    // summed area table of the per pixel difference
+    let mut sum = vec![0u64; ((w + 1) * (h + 1)) as usize];
+    let at = |x: u32, y: u32| (y * (w + 1) + x) as usize;
+    for y in 0..h {
+        for x in 0..w {
+            let d: u64 = a.get_pixel(x, y).0.iter().zip(b.get_pixel(x, y).0.iter())
+                .map(|(p, q)| (*p as i64 - *q as i64).unsigned_abs()).sum();
+            sum[at(x + 1, y + 1)] = d + sum[at(x, y + 1)] + sum[at(x + 1, y)] - sum[at(x, y)];
+        }
+    }
+
+    let mut best = (0, Crop { x: (w - size) / 2, y: (h - size) / 2, size });
+    for y in 0..=h - size {
+        for x in 0..=w - size {
+            let d = sum[at(x + size, y + size)] + sum[at(x, y)] - sum[at(x + size, y)] - sum[at(x, y + size)];
+            if d > best.0 {
+                best = (d, Crop { x, y, size });
+            }
+        }
+    }
+    best.1
+}
+
+// lays the panels out in rows of `columns` cells: the label, the image with the crop outlined
+// and the crop magnified `zoom` times below it
+pub fn compose(panels: &[Panel], crop: Crop, zoom: u32, columns: usize) -> RgbImage {
+    assert!(!panels.is_empty(), "nothing to compose");
+    let (w, h) = panels[0].image.dimensions();
+    assert!(panels.iter().all(|p| p.image.dimensions() == (w, h)), "panels differ in size");
+    assert!(crop.x + crop.size <= w && crop.y + crop.size <= h, "crop is outside the panels");
+
+    let label_h = 7 * TEXT_SCALE + MARGIN;
+    let zoomed = crop.size * zoom;
+    let cell_w = w.max(zoomed);
+    let cell_h = label_h + h + MARGIN + zoomed;
+    let columns = columns.clamp(1, panels.len()) as u32;
+    let rows = (panels.len() as u32).div_ceil(columns);
+
+    let mut out = RgbImage::from_pixel(MARGIN + columns * (cell_w + MARGIN), MARGIN + rows * (cell_h + MARGIN), BACKGROUND);
+    for (i, panel) in panels.iter().enumerate() {
+        let left = MARGIN + (i as u32 % columns) * (cell_w + MARGIN);
+        let top = MARGIN + (i as u32 / columns) * (cell_h + MARGIN);
+        draw_text(&mut out, left, top, &panel.label, TEXT_SCALE, TEXT);
+
+        let image_top = top + label_h;
+        for (x, y, p) in panel.image.enumerate_pixels() {
+            out.put_pixel(left + x, image_top + y, *p);
+        }
+        draw_rect(&mut out, left + crop.x, image_top + crop.y, crop.size, crop.size, OUTLINE);
+
+        let zoom_top = image_top + h + MARGIN;
+        for y in 0..zoomed {
+            for x in 0..zoomed {
+                let p = panel.image.get_pixel(crop.x + x / zoom, crop.y + y / zoom);
+                out.put_pixel(left + x, zoom_top + y, *p);
+            }
+        }
+        draw_rect(&mut out, left, zoom_top, zoomed, zoomed, OUTLINE);
+    }
+    out
+}
+
+fn draw_rect(img: &mut RgbImage, x: u32, y: u32, w: u32, h: u32, color: Rgb<u8>) {
+    let (iw, ih) = img.dimensions();
+    let mut put = |px: u32, py: u32| {
+        if px < iw && py < ih {
+            img.put_pixel(px, py, color);
+        }
+    };
+    for i in 0..w {
+        put(x + i, y);
+        put(x + i, y + h - 1);
+    }
+    for i in 0..h {
+        put(x, y + i);
+        put(x + w - 1, y + i);
+    }
+}
+
+// 5x7 bitmap text, lower case is drawn as upper case and unknown characters as blanks
+pub fn draw_text(img: &mut RgbImage, x: u32, y: u32, text: &str, scale: u32, color: Rgb<u8>) {
+    let (iw, ih) = img.dimensions();
+    for (i, c) in text.chars().enumerate() {
+        let rows = glyph(c.to_ascii_uppercase());
+        let gx = x + i as u32 * 6 * scale;
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..5 {
+                if bits & (0x10 >> col) == 0 {
+                    continue;
+                }
+                for sy in 0..scale {
+                    for sx in 0..scale {
+                        let (px, py) = (gx + col * scale + sx, y + row as u32 * scale + sy);
+                        if px < iw && py < ih {
+                            img.put_pixel(px, py, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        _ => [0; 7],
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::Path;
+use image::{ImageFormat, ImageResult, Rgb, RgbImage};
+use crate::utils::V3d;
+
+// frame_buf is stored row by row from the top, colors in [0, 255]
+pub fn frame_buffer2image(frame_buffer: &[V3d], width: u64, height: u64) -> RgbImage {
+    assert_eq!(frame_buffer.len() as u64, width * height, "frame buffer does not match {}x{}", width, height);
+    RgbImage::from_fn(width as u32, height as u32, |x, y| {
+        let color = frame_buffer[(y as u64 * width + x as u64) as usize];
+        Rgb([to_u8(color.x), to_u8(color.y), to_u8(color.z)])
+    })
+}
+
+// the format is picked from the file extension (png, ppm, bmp)
+pub fn save_image<P: AsRef<Path>>(path: P, frame_buffer: &[V3d], width: u64, height: u64) -> ImageResult<()> {
+    let format = ImageFormat::from_path(path.as_ref())?;
+    frame_buffer2image(frame_buffer, width, height).save_with_format(path, format)
+}
+
+pub fn save_png<P: AsRef<Path>>(path: P, frame_buffer: &[V3d], width: u64, height: u64) -> ImageResult<()> {
+    frame_buffer2image(frame_buffer, width, height).save_with_format(path, ImageFormat::Png)
+}
+
+pub fn save_ppm<P: AsRef<Path>>(path: P, frame_buffer: &[V3d], width: u64, height: u64) -> ImageResult<()> {
+    frame_buffer2image(frame_buffer, width, height).save_with_format(path, ImageFormat::Pnm)
+}
+
+pub fn save_bmp<P: AsRef<Path>>(path: P, frame_buffer: &[V3d], width: u64, height: u64) -> ImageResult<()> {
+    frame_buffer2image(frame_buffer, width, height).save_with_format(path, ImageFormat::Bmp)
+}
+
+fn to_u8(c: f64) -> u8 {
+    c.round().clamp(0.0, 255.0) as u8
+}
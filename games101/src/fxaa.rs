@@ -0,0 +1,184 @@
+use nalgebra::{Vector2, Vector3};
+
+// Rec. 601 weights, colors are 0..255 and luma is brought to 0..1
+const LUMA: Vector3<f64> = Vector3::new(0.299 / 255.0, 0.587 / 255.0, 0.114 / 255.0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FxaaQuality {
+    Low,
+    Medium,
+    High,
+    Extreme,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FxaaSettings {
+    // local contrast needed to count as an edge, relative to the brightest neighbour
+    pub edge_threshold: f64,
+    // contrast below this is never an edge, keeps dark areas from being filtered
+    pub edge_threshold_min: f64,
+    // 0 turns subpixel aliasing removal off, 1 is the softest
+    pub subpixel: f64,
+    // how far each step of the edge search moves along the edge, in pixels
+    pub search_steps: Vec<f64>,
+}
+
+impl Default for FxaaSettings {
+    fn default() -> Self {
+        FxaaSettings::preset(FxaaQuality::Medium)
+    }
+}
+
+impl FxaaSettings {
+    // roughly the FXAA 3.11 presets 12, 23, 29 and 39
+    pub fn preset(quality: FxaaQuality) -> Self {
+        let (edge_threshold, edge_threshold_min, subpixel, search_steps) = match quality {
+            FxaaQuality::Low => (0.25, 0.0833, 0.5, vec![1.0, 1.5, 2.0, 4.0, 12.0]),
+            FxaaQuality::Medium => (0.166, 0.0833, 0.75, vec![1.0, 1.5, 2.0, 2.0, 2.0, 2.0, 4.0, 8.0]),
+            FxaaQuality::High => (0.125, 0.0625, 0.75,
+                                  vec![1.0, 1.5, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 4.0, 8.0]),
+            FxaaQuality::Extreme => (0.063, 0.0312, 1.0,
+                                     vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.5, 2.0, 2.0, 2.0, 2.0, 4.0, 8.0]),
+        };
+        FxaaSettings { edge_threshold, edge_threshold_min, subpixel, search_steps }
+    }
+}
+
+// the image is stored row by row from the top, as Rasterizer::frame_buffer
+struct Image<'a> {
+    color: &'a [Vector3<f64>],
+    luma: Vec<f64>,
+    width: usize,
+    height: usize,
+}
+
+impl Image<'_> {
+    fn index(&self, x: i64, y: i64) -> usize {
+        let x = x.clamp(0, self.width as i64 - 1) as usize;
+        let y = y.clamp(0, self.height as i64 - 1) as usize;
+        y * self.width + x
+    }
+
+    fn luma_at(&self, x: i64, y: i64) -> f64 {
+        self.luma[self.index(x, y)]
+    }
+
+    // bilinear lookup with pixel centers at +0.5, edges are clamped
+    fn sample<T>(&self, p: Vector2<f64>, get: impl Fn(usize) -> T) -> T
+        where T: std::ops::Add<Output = T> + std::ops::Mul<f64, Output = T> {
+        let (fx, fy) = (p.x - 0.5, p.y - 0.5);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        get(self.index(x0, y0)) * ((1.0 - tx) * (1.0 - ty))
+            + get(self.index(x0 + 1, y0)) * (tx * (1.0 - ty))
+            + get(self.index(x0, y0 + 1)) * ((1.0 - tx) * ty)
+            + get(self.index(x0 + 1, y0 + 1)) * (tx * ty)
+    }
+
+    fn luma(&self, p: Vector2<f64>) -> f64 {
+        self.sample(p, |i| self.luma[i])
+    }
+
+    fn color(&self, p: Vector2<f64>) -> Vector3<f64> {
+        self.sample(p, |i| self.color[i])
+    }
+}
+
+// runs over the whole frame and returns the filtered copy
+pub fn fxaa(color: &[Vector3<f64>], width: usize, height: usize, settings: &FxaaSettings) -> Vec<Vector3<f64>> {
+    assert_eq!(color.len(), width * height, "frame buffer does not match {}x{}", width, height);
+    let image = Image { color, luma: color.iter().map(|c| c.dot(&LUMA)).collect(), width, height };
+
+    let mut out = Vec::with_capacity(color.len());
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            out.push(fxaa_pixel(&image, x, y, settings));
+        }
+    }
+    out
+}
+
+fn fxaa_pixel(image: &Image, x: i64, y: i64, settings: &FxaaSettings) -> Vector3<f64> {
+    let center = Vector2::new(x as f64 + 0.5, y as f64 + 0.5);
+    // y grows downwards here, so north is y - 1
+    let luma_m = image.luma_at(x, y);
+    let luma_n = image.luma_at(x, y - 1);
+    let luma_s = image.luma_at(x, y + 1);
+    let luma_w = image.luma_at(x - 1, y);
+    let luma_e = image.luma_at(x + 1, y);
+
+    let range_max = luma_m.max(luma_n).max(luma_s).max(luma_w).max(luma_e);
+    let range_min = luma_m.min(luma_n).min(luma_s).min(luma_w).min(luma_e);
+    let range = range_max - range_min;
+    if range < settings.edge_threshold_min.max(range_max * settings.edge_threshold) {
+        return image.color[image.index(x, y)];
+    }
+
+    let luma_nw = image.luma_at(x - 1, y - 1);
+    let luma_ne = image.luma_at(x + 1, y - 1);
+    let luma_sw = image.luma_at(x - 1, y + 1);
+    let luma_se = image.luma_at(x + 1, y + 1);
+
+    // subpixel blend from how much the center differs from its neighbourhood
+    let luma_l = (2.0 * (luma_n + luma_s + luma_w + luma_e) + luma_nw + luma_ne + luma_sw + luma_se) / 12.0;
+    let subpix_a = ((luma_l - luma_m).abs() / range).clamp(0.0, 1.0);
+    let subpix_b = (-2.0 * subpix_a + 3.0) * subpix_a * subpix_a;
+    let subpix = subpix_b * subpix_b * settings.subpixel;
+
+    // a horizontal edge changes along y
+    let edge_horz = (luma_nw - 2.0 * luma_w + luma_sw).abs()
+        + 2.0 * (luma_n - 2.0 * luma_m + luma_s).abs()
+        + (luma_ne - 2.0 * luma_e + luma_se).abs();
+    let edge_vert = (luma_nw - 2.0 * luma_n + luma_ne).abs()
+        + 2.0 * (luma_w - 2.0 * luma_m + luma_e).abs()
+        + (luma_sw - 2.0 * luma_s + luma_se).abs();
+    let horz = edge_horz >= edge_vert;
+
+    // the normal points across the edge, the search runs along it
+    let (luma_neg, luma_pos, normal, along) = if horz {
+        (luma_n, luma_s, Vector2::new(0.0, 1.0), Vector2::new(1.0, 0.0))
+    } else {
+        (luma_w, luma_e, Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0))
+    };
+    let gradient_neg = luma_neg - luma_m;
+    let gradient_pos = luma_pos - luma_m;
+    let pair_neg = gradient_neg.abs() >= gradient_pos.abs();
+    let gradient_scaled = gradient_neg.abs().max(gradient_pos.abs()) / 4.0;
+    let (length_sign, luma_pair) = if pair_neg { (-1.0, luma_neg + luma_m) } else { (1.0, luma_pos + luma_m) };
+
+    // start on the edge between the center and the neighbour it contrasts most with
+    let on_edge = center + normal * (0.5 * length_sign);
+    let luma_mm = luma_m - luma_pair * 0.5;
+
+    let mut pos_n = on_edge;
+    let mut pos_p = on_edge;
+    let (mut end_n, mut end_p) = (0.0, 0.0);
+    let (mut done_n, mut done_p) = (false, false);
+    for step in &settings.search_steps {
+        if !done_n {
+            pos_n -= along * *step;
+            end_n = image.luma(pos_n) - luma_pair * 0.5;
+            done_n = end_n.abs() >= gradient_scaled;
+        }
+        if !done_p {
+            pos_p += along * *step;
+            end_p = image.luma(pos_p) - luma_pair * 0.5;
+            done_p = end_p.abs() >= gradient_scaled;
+        }
+        if done_n && done_p {
+            break;
+        }
+    }
+
+    let dst_n = (center - pos_n).dot(&along);
+    let dst_p = (pos_p - center).dot(&along);
+    let direction_n = dst_n < dst_p;
+    let dst = dst_n.min(dst_p);
+    // only blend when the end of the span goes the other way than the center does
+    let good_span = if direction_n { (end_n < 0.0) != (luma_mm < 0.0) } else { (end_p < 0.0) != (luma_mm < 0.0) };
+    let pixel_offset = if good_span { 0.5 - dst / (dst_n + dst_p) } else { 0.0 };
+    let offset = pixel_offset.max(subpix);
+
+    image.color(center + normal * (offset * length_sign))
+}
@@ -0,0 +1,20 @@
+pub mod triangle;
+pub mod rasterizer;
+pub mod clip;
+pub mod msaa;
+pub mod fxaa;
+pub mod taa;
+pub mod ssaa;
+pub mod line;
+pub mod shader;
+pub mod lighting;
+pub mod texture;
+pub mod mesh;
+pub mod obj;
+pub mod ply;
+pub mod stl;
+pub mod camera;
+pub mod transform;
+pub mod utils;
+pub mod export;
+pub mod compare;
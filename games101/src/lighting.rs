@@ -0,0 +1,124 @@
+use std::ops::{Add, Mul};
+use nalgebra::{Matrix4, Vector3, Vector4};
+
+// intensities are per color channel, as in assignment 3
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Light {
+    // falls off with the squared distance
+    Point { position: Vector3<f64>, intensity: Vector3<f64> },
+    // the direction the light travels in, e.g. (0, -1, 0) shines straight down
+    Directional { direction: Vector3<f64>, intensity: Vector3<f64> },
+}
+
+impl Light {
+    // the same light after the view matrix, positions and directions are in world space otherwise
+    pub fn to_view(&self, view: &Matrix4<f64>) -> Light {
+        match *self {
+            Light::Point { position, intensity } => Light::Point {
+                position: (view * Vector4::new(position.x, position.y, position.z, 1.0)).xyz(),
+                intensity,
+            },
+            Light::Directional { direction, intensity } => Light::Directional {
+                direction: (view * Vector4::new(direction.x, direction.y, direction.z, 0.0)).xyz(),
+                intensity,
+            },
+        }
+    }
+}
+
+// the diffuse color kd is the interpolated vertex color
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Material {
+    pub ka: Vector3<f64>,
+    pub ks: Vector3<f64>,
+    pub shininess: f64,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material {
+            ka: Vector3::new(0.005, 0.005, 0.005),
+            ks: Vector3::new(0.7937, 0.7937, 0.7937),
+            shininess: 150.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lighting {
+    pub ambient: Vector3<f64>,
+    pub lights: Vec<Light>,
+    pub material: Material,
+}
+
+impl Default for Lighting {
+    // the two lights of assignment 3
+    fn default() -> Self {
+        Lighting {
+            ambient: Vector3::new(10.0, 10.0, 10.0),
+            lights: vec![
+                Light::Point { position: Vector3::new(20.0, 20.0, 20.0), intensity: Vector3::new(500.0, 500.0, 500.0) },
+                Light::Point { position: Vector3::new(-20.0, 20.0, 0.0), intensity: Vector3::new(500.0, 500.0, 500.0) },
+            ],
+            material: Material::default(),
+        }
+    }
+}
+
+// a point on a surface in view space, the camera sits at the origin
+#[derive(Clone, Copy, Debug)]
+pub struct Surface {
+    pub position: Vector3<f64>,
+    pub normal: Vector3<f64>,
+    // 0..255
+    pub color: Vector3<f64>,
+}
+
+impl Add for Surface {
+    type Output = Surface;
+
+    fn add(self, other: Surface) -> Surface {
+        Surface {
+            position: self.position + other.position,
+            normal: self.normal + other.normal,
+            color: self.color + other.color,
+        }
+    }
+}
+
+impl Mul<f64> for Surface {
+    type Output = Surface;
+
+    fn mul(self, k: f64) -> Surface {
+        Surface { position: self.position * k, normal: self.normal * k, color: self.color * k }
+    }
+}
+
+impl Lighting {
+    pub fn to_view(&self, view: &Matrix4<f64>) -> Lighting {
+        Lighting { lights: self.lights.iter().map(|l| l.to_view(view)).collect(), ..self.clone() }
+    }
+
+    // Blinn-Phong, the lights have to be in the same space as the surface, returns 0..255
+    pub fn shade(&self, surface: &Surface) -> Vector3<f64> {
+        let m = &self.material;
+        let kd = surface.color / 255.0;
+        let n = surface.normal.normalize();
+        let v = (-surface.position).normalize();
+
+        let mut color = m.ka.component_mul(&self.ambient);
+        for light in &self.lights {
+            let (l, intensity) = match *light {
+                Light::Point { position, intensity } => {
+                    let l = position - surface.position;
+                    (l.normalize(), intensity / l.norm_squared())
+                }
+                Light::Directional { direction, intensity } => (-direction.normalize(), intensity),
+            };
+            let h = (l + v).normalize();
+            color += kd.component_mul(&intensity) * n.dot(&l).max(0.0);
+            color += m.ks.component_mul(&intensity) * n.dot(&h).max(0.0).powf(m.shininess);
+        }
+        (color * 255.0).map(|c| c.clamp(0.0, 255.0))
+    }
+}
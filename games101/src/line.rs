@@ -0,0 +1,151 @@
+use nalgebra::{Vector2, Vector3};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineMode {
+    // hard edged, one pixel wide lines are stepped with Bresenham
+    #[default]
+    Bresenham,
+    // pixels are blended by how much of them the line covers
+    Antialiased,
+}
+
+// what the open ends of a line look like
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    // ends exactly at the end point
+    #[default]
+    Butt,
+    // carries on for half the width
+    Square,
+    Round,
+}
+
+// how two segments of a strip meet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    // falls back to a bevel when the tip is longer than miter_limit half widths
+    #[default]
+    Miter,
+    Bevel,
+    Round,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineStyle {
+    pub mode: LineMode,
+    // for wireframes drawn without a color buffer and for overlays, 0..255
+    pub color: Vector3<f64>,
+    // in pixels
+    pub width: f64,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f64,
+    // lines are moved this fraction of the depth range towards the camera, plus slope_bias times
+    // the steepest depth change per pixel of the face an edge belongs to, so wireframes drawn
+    // over their own faces do not z-fight
+    pub depth_bias: f64,
+    pub slope_bias: f64,
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        LineStyle {
+            mode: LineMode::Bresenham,
+            color: Vector3::new(0.0, 255.0, 0.0),
+            width: 1.0,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            depth_bias: 1e-5,
+            slope_bias: 1.0,
+        }
+    }
+}
+
+// how far outside the shape of a line a pixel can still get some coverage, in pixels
+pub fn reach(style: &LineStyle) -> f64 {
+    let r = style.width / 2.0;
+    match style.join {
+        LineJoin::Miter => r * style.miter_limit.max(1.0) + 1.0,
+        _ => r + 1.0,
+    }
+}
+
+// coverage of the pixel centered at p by the segment a-b, and where along it p is (0 at a, 1 at b)
+pub fn segment_coverage(p: Vector2<f64>, a: Vector2<f64>, b: Vector2<f64>, half_width: f64,
+                        start: LineCap, end: LineCap) -> (f64, f64) {
+    let len = (b - a).norm();
+    let d = if len > 1e-9 { (b - a) / len } else { Vector2::new(1.0, 0.0) };
+    let n = Vector2::new(-d.y, d.x);
+    let u = (p - a).dot(&d);
+    let v = (p - a).dot(&n);
+    let t = if len > 1e-9 { (u / len).clamp(0.0, 1.0) } else { 0.0 };
+
+    // the body between the end points, each end is softened by its own cap
+    let across = (half_width + 0.5 - v.abs()).clamp(0.0, 1.0);
+    let extend = |cap: LineCap| if cap == LineCap::Square { half_width } else { 0.0 };
+    let along = ((u + extend(start)).min(len - u + extend(end)) + 0.5).clamp(0.0, 1.0);
+    let mut coverage = across * along;
+
+    // round caps are a disk on the end point
+    for (cap, center) in [(start, a), (end, b)] {
+        if cap == LineCap::Round {
+            coverage = coverage.max(disk_coverage(p, center, half_width));
+        }
+    }
+    (coverage, t)
+}
+
+fn disk_coverage(p: Vector2<f64>, center: Vector2<f64>, radius: f64) -> f64 {
+    (radius + 0.5 - (p - center).norm()).clamp(0.0, 1.0)
+}
+
+// coverage of the piece that fills the outer corner at v, where the strip turns from d_in to d_out
+pub fn join_coverage(p: Vector2<f64>, v: Vector2<f64>, d_in: Vector2<f64>, d_out: Vector2<f64>,
+                     half_width: f64, join: LineJoin, miter_limit: f64) -> f64 {
+    let cross = d_in.x * d_out.y - d_in.y * d_out.x;
+    if cross.abs() < 1e-9 && d_in.dot(&d_out) > 0.0 {
+        return 0.0;
+    }
+    if join == LineJoin::Round {
+        return disk_coverage(p, v, half_width);
+    }
+
+    // the normals on the outside of the turn
+    let side = if cross > 0.0 { -1.0 } else { 1.0 };
+    let o1 = Vector2::new(-d_in.y, d_in.x) * side;
+    let o2 = Vector2::new(-d_out.y, d_out.x) * side;
+    let (c1, c2) = (v + o1 * half_width, v + o2 * half_width);
+
+    let bisector = o1 + o2;
+    let miter = if bisector.norm() > 1e-9 { half_width / bisector.normalize().dot(&o1) } else { f64::INFINITY };
+    if join == LineJoin::Miter && miter <= miter_limit * half_width {
+        let tip = v + bisector.normalize() * miter;
+        polygon_coverage(p, &[v, c1, tip, c2])
+    } else {
+        polygon_coverage(p, &[v, c1, c2])
+    }
+}
+
+// approximate coverage of a convex polygon from the distance to its farthest edge line
+fn polygon_coverage(p: Vector2<f64>, polygon: &[Vector2<f64>]) -> f64 {
+    let area: f64 = (0..polygon.len()).map(|i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        a.x * b.y - a.y * b.x
+    }).sum();
+    if area.abs() < 1e-12 {
+        return 0.0;
+    }
+    let mut distance = f64::NEG_INFINITY;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        let e = b - a;
+        if e.norm() < 1e-12 {
+            continue;
+        }
+        // outward normal for either winding
+        let n = Vector2::new(e.y, -e.x).normalize() * area.signum();
+        distance = distance.max((p - a).dot(&n));
+    }
+    (0.5 - distance).clamp(0.0, 1.0)
+}
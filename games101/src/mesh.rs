@@ -0,0 +1,101 @@
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use nalgebra::{Vector2, Vector3};
+use crate::rasterizer::{ColBufId, IndBufId, NormBufId, PosBufId, TexBufId};
+use crate::utils::V3d;
+
+// triangles that share a name and a material, e.g. one `g` or `usemtl` block of an obj file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    pub name: String,
+    pub material: Option<String>,
+    // into Mesh::indices
+    pub triangles: Range<usize>,
+}
+
+// indexed triangles ready for the rasterizer buffers, one entry per vertex in every list
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<V3d>,
+    // 0..255
+    pub colors: Vec<V3d>,
+    // empty when the file had none
+    pub normals: Vec<V3d>,
+    pub tex_coords: Vec<Vector2<f64>>,
+    pub indices: Vec<Vector3<usize>>,
+    pub groups: Vec<Group>,
+}
+
+impl Mesh {
+    // a single group and no normals or texture coordinates, as read from a ply or stl file
+    pub fn new(positions: Vec<V3d>, colors: Vec<V3d>, indices: Vec<Vector3<usize>>) -> Self {
+        let groups = vec![Group { name: "default".to_string(), material: None, triangles: 0..indices.len() }];
+        Mesh { positions, colors, normals: Vec::new(), tex_coords: Vec::new(), indices, groups }
+    }
+}
+
+// reading a ply or stl file went wrong
+#[derive(Debug)]
+pub enum MeshError {
+    Io(io::Error),
+    Malformed(String),
+    // a face refers to a vertex that is not there
+    IndexOutOfRange { index: usize, count: usize },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MeshError::Io(e) => write!(f, "{}", e),
+            MeshError::Malformed(message) => write!(f, "{}", message),
+            MeshError::IndexOutOfRange { index, count } =>
+                write!(f, "vertex index {} is out of range, there are {}", index, count),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MeshError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MeshError {
+    fn from(e: io::Error) -> Self {
+        MeshError::Io(e)
+    }
+}
+
+// what Rasterizer::load_mesh hands back, pass them on to draw, draw_lit or draw_textured
+#[derive(Clone)]
+pub struct MeshBuffers {
+    pub position: PosBufId,
+    pub indices: IndBufId,
+    pub colors: ColBufId,
+    pub normals: Option<NormBufId>,
+    pub tex_coords: Option<TexBufId>,
+    // the indices of each of Mesh::groups on their own
+    pub groups: Vec<IndBufId>,
+}
+
+// the normal of a triangle scaled by twice its area, counter clockwise is the front
+pub fn face_normal(positions: &[V3d], t: &Vector3<usize>) -> V3d {
+    let (a, b, c) = (positions[t.x], positions[t.y], positions[t.z]);
+    (b - a).cross(&(c - a))
+}
+
+// the area weighted average of the face normals around every vertex
+pub fn smooth_normals(positions: &[V3d], indices: &[Vector3<usize>]) -> Vec<V3d> {
+    let mut normals = vec![V3d::zeros(); positions.len()];
+    for t in indices {
+        let n = face_normal(positions, t);
+        for &i in t.iter() {
+            normals[i] += n;
+        }
+    }
+    normals.iter().map(|n| n.try_normalize(1e-12).unwrap_or_else(V3d::zeros)).collect()
+}
@@ -0,0 +1,108 @@
+use nalgebra::Vector2;
+
+pub const SAMPLE_COUNTS: [usize; 5] = [1, 2, 4, 8, 16];
+
+// where the samples sit inside a pixel, offsets are from the lower left corner in [0, 1)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SamplePattern {
+    #[default]
+    OrderedGrid,
+    RotatedGrid,
+    // the standard D3D sample positions
+    Standard,
+    Custom(Vec<Vector2<f64>>),
+}
+
+impl SamplePattern {
+    pub fn offsets(&self, count: usize) -> Vec<Vector2<f64>> {
+        assert!(SAMPLE_COUNTS.contains(&count), "unsupported MSAA sample count {}", count);
+        match self {
+            SamplePattern::OrderedGrid => {
+                let (cols, rows) = grid(count);
+                (0..count).map(|i| {
+                    let (c, r) = (i % cols, i / cols);
+                    Vector2::new((c as f64 + 0.5) / cols as f64, (r as f64 + 0.5) / rows as f64)
+                }).collect()
+            }
+            SamplePattern::RotatedGrid => {
+                // the ordered grid sheared so no two samples share a row or column, 4 gives RGSS
+                let (cols, rows) = grid(count);
+                (0..count).map(|i| {
+                    let (c, r) = (i % cols, i / cols);
+                    Vector2::new((rows * c + rows - 1 - r) as f64 + 0.5, (cols * r + c) as f64 + 0.5) / count as f64
+                }).collect()
+            }
+            SamplePattern::Standard => {
+                // D3D positions are in 1/16 pixel from the center, on [-8, 8) with y pointing down.
+                // flipping y would put -8 on the top edge, outside [0, 1), so the table is taken as
+                // it is, which mirrors the pattern top to bottom and keeps its spacing
+                standard_positions(count).iter()
+                    .map(|&(x, y)| Vector2::new((x + 8) as f64 / 16.0, (y + 8) as f64 / 16.0))
+                    .collect()
+            }
+            SamplePattern::Custom(offsets) => {
+                assert_eq!(offsets.len(), count, "custom MSAA pattern has {} offsets, expected {}", offsets.len(), count);
+                assert!(offsets.iter().all(|o| (0.0..1.0).contains(&o.x) && (0.0..1.0).contains(&o.y)),
+                        "custom MSAA offsets must lie in [0, 1)");
+                offsets.clone()
+            }
+        }
+    }
+}
+
+// columns and rows of the grid the count is laid out on
+fn grid(count: usize) -> (usize, usize) {
+    match count {
+        1 => (1, 1),
+        2 => (2, 1),
+        4 => (2, 2),
+        8 => (4, 2),
+        _ => (4, 4),
+    }
+}
+
+fn standard_positions(count: usize) -> &'static [(i32, i32)] {
+    match count {
+        1 => &[(0, 0)],
+        2 => &[(4, 4), (-4, -4)],
+        4 => &[(-2, -6), (6, -2), (-6, 2), (2, 6)],
+        8 => &[(1, -3), (-1, 3), (5, 1), (-3, -5), (-5, 5), (-7, -1), (3, 7), (7, -7)],
+        _ => &[(1, 1), (-1, -3), (-3, 2), (4, -1), (-5, -2), (2, 5), (5, 3), (3, -5),
+               (-2, 6), (0, -7), (-4, -6), (-6, 4), (-8, 0), (7, -4), (6, 7), (-7, -8)],
+    }
+}
+
+// reconstruction filter used by Rasterizer::resolve, weights are by distance from the pixel center
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResolveFilter {
+    // plain average of the pixel's own samples
+    #[default]
+    Box,
+    // linear falloff over one pixel, also takes in the neighbours' samples
+    Tent,
+    // sigma of half a pixel, cut off at three sigma
+    Gaussian,
+}
+
+impl ResolveFilter {
+    // how far from the pixel center a sample can still count
+    pub fn radius(&self) -> f64 {
+        match self {
+            ResolveFilter::Box => 0.5,
+            ResolveFilter::Tent => 1.0,
+            ResolveFilter::Gaussian => 1.5,
+        }
+    }
+
+    pub fn weight(&self, dx: f64, dy: f64) -> f64 {
+        let r = self.radius();
+        if dx.abs() > r || dy.abs() > r {
+            return 0.0;
+        }
+        match self {
+            ResolveFilter::Box => 1.0,
+            ResolveFilter::Tent => (1.0 - dx.abs()) * (1.0 - dy.abs()),
+            ResolveFilter::Gaussian => (-(dx * dx + dy * dy) / (2.0 * 0.5 * 0.5)).exp(),
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use nalgebra::{Vector2, Vector3};
+use crate::mesh::{smooth_normals, Group, Mesh};
+use crate::utils::V3d;
+
+// lines are counted from 1
+#[derive(Debug)]
+pub enum ObjError {
+    Io(PathBuf, io::Error),
+    Malformed { line: usize, message: String },
+    // a face refers to a position, texture coordinate or normal that is not there (yet)
+    IndexOutOfRange { line: usize, kind: &'static str, index: i64, count: usize },
+    // something is wrong inside a material library named by mtllib
+    Mtl(PathBuf, Box<ObjError>),
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            ObjError::Malformed { line, message } => write!(f, "line {}: {}", line, message),
+            ObjError::IndexOutOfRange { line, kind, index, count } =>
+                write!(f, "line {}: {} index {} is out of range, there are {}", line, kind, index, count),
+            ObjError::Mtl(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjError::Io(_, e) => Some(e),
+            ObjError::Mtl(_, e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// one newmtl block, colors are 0..1 as in the file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    pub name: String,
+    pub ka: V3d,
+    pub kd: V3d,
+    pub ks: V3d,
+    pub ns: f64,
+    // map_Kd, relative to the mtl file unless it was read by load_obj
+    pub diffuse_map: Option<PathBuf>,
+}
+
+impl Material {
+    fn new(name: &str) -> Self {
+        Material {
+            name: name.to_string(),
+            ka: V3d::zeros(),
+            kd: V3d::new(1.0, 1.0, 1.0),
+            ks: V3d::zeros(),
+            ns: 0.0,
+            diffuse_map: None,
+        }
+    }
+
+    // for Rasterizer::set_lighting, the diffuse color goes into the color buffer
+    pub fn to_lighting(&self) -> crate::lighting::Material {
+        crate::lighting::Material { ka: self.ka, ks: self.ks, shininess: self.ns }
+    }
+}
+
+// the next statement split off a line, without the comment
+fn statement(line: &str) -> Option<(&str, &str)> {
+    let line = line.split('#').next().unwrap().trim();
+    if line.is_empty() {
+        return None;
+    }
+    Some(line.split_once(char::is_whitespace).map_or((line, ""), |(k, rest)| (k, rest.trim())))
+}
+
+fn malformed(line: usize, message: String) -> ObjError {
+    ObjError::Malformed { line, message }
+}
+
+// between min and max numbers after the keyword
+fn numbers<T: FromStr>(line: usize, keyword: &str, rest: &str, min: usize, max: usize) -> Result<Vec<T>, ObjError> {
+    let values = rest.split_whitespace()
+        .map(|s| s.parse().map_err(|_| malformed(line, format!("`{}` is not a number in `{}`", s, keyword))))
+        .collect::<Result<Vec<T>, _>>()?;
+    if values.len() < min || values.len() > max {
+        let expected = if min == max { min.to_string() } else { format!("{} to {}", min, max) };
+        return Err(malformed(line, format!("`{}` takes {} numbers, got {}", keyword, expected, values.len())));
+    }
+    Ok(values)
+}
+
+fn color(line: usize, keyword: &str, rest: &str) -> Result<V3d, ObjError> {
+    // a single value is a gray
+    match numbers::<f64>(line, keyword, rest, 1, 3)?[..] {
+        [c] => Ok(V3d::repeat(c)),
+        [r, g, b] => Ok(V3d::new(r, g, b)),
+        _ => Err(malformed(line, format!("`{}` takes r g b or a single gray", keyword))),
+    }
+}
+
+// the material libraries an obj file refers to, statements it does not know are skipped
+pub fn parse_mtl(src: &str) -> Result<Vec<Material>, ObjError> {
+    let mut materials: Vec<Material> = Vec::new();
+    for (i, text) in src.lines().enumerate() {
+        let line = i + 1;
+        let Some((keyword, rest)) = statement(text) else { continue };
+        if keyword == "newmtl" {
+            if rest.is_empty() {
+                return Err(malformed(line, "`newmtl` without a name".to_string()));
+            }
+            materials.push(Material::new(rest));
+            continue;
+        }
+        let known = ["Ka", "Kd", "Ks", "Ns", "map_Kd"];
+        if !known.contains(&keyword) {
+            continue;
+        }
+        let Some(m) = materials.last_mut() else {
+            return Err(malformed(line, format!("`{}` before any `newmtl`", keyword)));
+        };
+        match keyword {
+            "Ka" => m.ka = color(line, keyword, rest)?,
+            "Kd" => m.kd = color(line, keyword, rest)?,
+            "Ks" => m.ks = color(line, keyword, rest)?,
+            "Ns" => m.ns = numbers(line, keyword, rest, 1, 1)?[0],
+            // options like -s 1 1 1 come first, the file name is last
+            _ => match rest.split_whitespace().last() {
+                Some(file) => m.diffuse_map = Some(PathBuf::from(file)),
+                None => return Err(malformed(line, "`map_Kd` without a file".to_string())),
+            },
+        }
+    }
+    Ok(materials)
+}
+
+// obj indices start at 1, negative ones count back from the last element read so far
+fn resolve(line: usize, kind: &'static str, s: &str, count: usize) -> Result<usize, ObjError> {
+    let index: i64 = s.parse().map_err(|_| malformed(line, format!("`{}` is not a {} index", s, kind)))?;
+    let resolved = if index < 0 { count as i64 + index } else { index - 1 };
+    if index == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(ObjError::IndexOutOfRange { line, kind, index, count });
+    }
+    Ok(resolved as usize)
+}
+
+// a face corner is v, v/vt, v//vn or v/vt/vn
+type Corner = (usize, Option<usize>, Option<usize>);
+
+struct Parser<'a> {
+    materials: &'a HashMap<String, Material>,
+    positions: Vec<V3d>,
+    // from `v x y z r g b`, 0..1
+    vertex_colors: Vec<Option<V3d>>,
+    tex_coords: Vec<Vector2<f64>>,
+    normals: Vec<V3d>,
+    mesh: Mesh,
+    // where each vertex of the mesh came from, per group so every group can have its own color
+    vertices: HashMap<(usize, Corner), usize>,
+    sources: Vec<Corner>,
+    group: Group,
+}
+
+impl<'a> Parser<'a> {
+    fn corner(&self, line: usize, s: &str) -> Result<Corner, ObjError> {
+        let mut parts = s.split('/');
+        let v = resolve(line, "position", parts.next().unwrap(), self.positions.len())?;
+        let optional = |part: Option<&str>, kind, count| match part {
+            None | Some("") => Ok(None),
+            Some(s) => resolve(line, kind, s, count).map(Some),
+        };
+        let vt = optional(parts.next(), "texture coordinate", self.tex_coords.len())?;
+        let vn = optional(parts.next(), "normal", self.normals.len())?;
+        if parts.next().is_some() {
+            return Err(malformed(line, format!("`{}` has more than three indices", s)));
+        }
+        Ok((v, vt, vn))
+    }
+
+    fn vertex(&mut self, corner: Corner) -> usize {
+        let key = (self.mesh.groups.len(), corner);
+        if let Some(&i) = self.vertices.get(&key) {
+            return i;
+        }
+        let (v, vt, _) = corner;
+        let kd = match &self.group.material {
+            Some(name) => self.materials[name].kd,
+            None => V3d::new(1.0, 1.0, 1.0),
+        };
+        self.mesh.positions.push(self.positions[v]);
+        // colors are 0..1 in the files, some exporters write 0..255 or Kd above 1 and those are clamped
+        let color = self.vertex_colors[v].unwrap_or(kd).map(|c| c.clamp(0.0, 1.0));
+        self.mesh.colors.push(color * 255.0);
+        self.mesh.tex_coords.push(vt.map_or(Vector2::zeros(), |t| self.tex_coords[t]));
+        self.sources.push(corner);
+        let i = self.mesh.positions.len() - 1;
+        self.vertices.insert(key, i);
+        i
+    }
+
+    // fans out from the first corner, so the polygon should be convex
+    fn face(&mut self, line: usize, rest: &str) -> Result<(), ObjError> {
+        let corners = rest.split_whitespace().map(|s| self.corner(line, s)).collect::<Result<Vec<_>, _>>()?;
+        if corners.len() < 3 {
+            return Err(malformed(line, format!("a face needs at least 3 corners, got {}", corners.len())));
+        }
+        let first = self.vertex(corners[0]);
+        for pair in corners[1..].windows(2) {
+            let (b, c) = (self.vertex(pair[0]), self.vertex(pair[1]));
+            self.mesh.indices.push(Vector3::new(first, b, c));
+        }
+        Ok(())
+    }
+
+    // starts a new group from here on, unless the current one is still empty. names and
+    // materials that are not given carry over
+    fn start_group(&mut self, name: Option<&str>, material: Option<String>) {
+        let end = self.mesh.indices.len();
+        let mut next = Group {
+            name: name.map_or_else(|| self.group.name.clone(), str::to_string),
+            material: material.or_else(|| self.group.material.clone()),
+            triangles: end..end,
+        };
+        if self.group.triangles.start < end {
+            std::mem::swap(&mut self.group, &mut next);
+            next.triangles.end = end;
+            self.mesh.groups.push(next);
+        } else {
+            self.group = next;
+        }
+    }
+
+    fn finish(mut self) -> Mesh {
+        self.start_group(None, None);
+        // duplicated vertices share the smooth normal of their position
+        let by_position: Vec<_> = self.mesh.indices.iter().map(|t| t.map(|i| self.sources[i].0)).collect();
+        let smooth = smooth_normals(&self.positions, &by_position);
+        self.mesh.normals = self.sources.iter().map(|&(v, _, vn)| vn.map_or(smooth[v], |n| self.normals[n])).collect();
+        if self.tex_coords.is_empty() {
+            self.mesh.tex_coords.clear();
+        }
+        self.mesh
+    }
+}
+
+// an obj file already in memory, `usemtl` names have to be in `materials`, mtllib is skipped.
+// vertices are shared between faces when their position, texture coordinate and normal are,
+// corners without a normal get the smooth normal of their position
+pub fn parse_obj(src: &str, materials: &HashMap<String, Material>) -> Result<Mesh, ObjError> {
+    let mut p = Parser {
+        materials,
+        positions: Vec::new(),
+        vertex_colors: Vec::new(),
+        tex_coords: Vec::new(),
+        normals: Vec::new(),
+        mesh: Mesh::default(),
+        vertices: HashMap::new(),
+        sources: Vec::new(),
+        group: Group { name: "default".to_string(), material: None, triangles: 0..0 },
+    };
+    for (i, text) in src.lines().enumerate() {
+        let line = i + 1;
+        let Some((keyword, rest)) = statement(text) else { continue };
+        match keyword {
+            "v" => {
+                // an optional w is dropped, some exporters put a color after the position
+                let v = numbers::<f64>(line, keyword, rest, 3, 6)?;
+                if v.len() == 5 {
+                    return Err(malformed(line, "`v` takes x y z [w] or x y z r g b".to_string()));
+                }
+                p.positions.push(V3d::new(v[0], v[1], v[2]));
+                p.vertex_colors.push((v.len() == 6).then(|| V3d::new(v[3], v[4], v[5])));
+            }
+            "vt" => {
+                let t = numbers::<f64>(line, keyword, rest, 1, 3)?;
+                p.tex_coords.push(Vector2::new(t[0], t.get(1).copied().unwrap_or(0.0)));
+            }
+            "vn" => {
+                let n = numbers::<f64>(line, keyword, rest, 3, 3)?;
+                p.normals.push(V3d::new(n[0], n[1], n[2]));
+            }
+            "f" => p.face(line, rest)?,
+            "g" | "o" => p.start_group(Some(if rest.is_empty() { "default" } else { rest }), None),
+            "usemtl" => {
+                if !materials.contains_key(rest) {
+                    return Err(malformed(line, format!("unknown material `{}`", rest)));
+                }
+                p.start_group(None, Some(rest.to_string()));
+            }
+            // smoothing groups, lines, points, curves and mtllib
+            _ => {}
+        }
+    }
+    Ok(p.finish())
+}
+
+fn read(path: &Path) -> Result<String, ObjError> {
+    std::fs::read_to_string(path).map_err(|e| ObjError::Io(path.to_path_buf(), e))
+}
+
+// reads the obj file and the material libraries it names next to it, texture paths of the
+// materials are made relative to the working directory
+pub fn load_obj<P: AsRef<Path>>(path: P) -> Result<(Mesh, HashMap<String, Material>), ObjError> {
+    let src = read(path.as_ref())?;
+    let dir = path.as_ref().parent().unwrap_or(Path::new(""));
+    let mut materials = HashMap::new();
+    for text in src.lines() {
+        let Some(("mtllib", rest)) = statement(text) else { continue };
+        for name in rest.split_whitespace() {
+            let mtl = dir.join(name);
+            let parsed = parse_mtl(&read(&mtl)?).map_err(|e| ObjError::Mtl(mtl.clone(), Box::new(e)))?;
+            let mtl_dir = mtl.parent().unwrap_or(Path::new(""));
+            for mut m in parsed {
+                m.diffuse_map = m.diffuse_map.map(|p| mtl_dir.join(p));
+                materials.insert(m.name.clone(), m);
+            }
+        }
+    }
+    let mesh = parse_obj(&src, &materials)?;
+    Ok((mesh, materials))
+}
@@ -0,0 +1,322 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use nalgebra::{Vector2, Vector3};
+use crate::mesh::{Mesh, MeshError};
+use crate::utils::V3d;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Result<Scalar, MeshError> {
+        Ok(match name {
+            "char" | "int8" => Scalar::I8,
+            "uchar" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return Err(malformed(format!("unknown property type `{}`", name))),
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+
+    // the value that stands for full intensity in a color channel of this type
+    fn color_max(self) -> f64 {
+        match self {
+            Scalar::I8 => i8::MAX as f64,
+            Scalar::U8 => u8::MAX as f64,
+            Scalar::I16 => i16::MAX as f64,
+            Scalar::U16 => u16::MAX as f64,
+            Scalar::I32 => i32::MAX as f64,
+            Scalar::U32 => u32::MAX as f64,
+            Scalar::F32 | Scalar::F64 => 1.0,
+        }
+    }
+}
+
+enum Property {
+    Scalar(String, Scalar),
+    // the type of the count, then of the items
+    List(String, Scalar, Scalar),
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+fn malformed(message: String) -> MeshError {
+    MeshError::Malformed(message)
+}
+
+// where the values after end_header come from
+enum Body<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary { data: &'a [u8], big_endian: bool },
+}
+
+impl<'a> Body<'a> {
+    // every type fits into a double without loss
+    fn read(&mut self, ty: Scalar) -> Result<f64, MeshError> {
+        match self {
+            Body::Ascii(tokens) => {
+                let token = tokens.next().ok_or_else(|| malformed("the file ends in the middle of the data".to_string()))?;
+                token.parse().map_err(|_| malformed(format!("`{}` is not a number", token)))
+            }
+            Body::Binary { data, big_endian } => {
+                let n = ty.size();
+                if data.len() < n {
+                    return Err(malformed("the file ends in the middle of the data".to_string()));
+                }
+                let mut b = [0u8; 8];
+                b[..n].copy_from_slice(&data[..n]);
+                *data = &data[n..];
+                if *big_endian {
+                    b[..n].reverse();
+                }
+                Ok(match ty {
+                    Scalar::I8 => b[0] as i8 as f64,
+                    Scalar::U8 => b[0] as f64,
+                    Scalar::I16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+                    Scalar::U16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+                    Scalar::I32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    Scalar::U32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    Scalar::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    Scalar::F64 => f64::from_le_bytes(b),
+                })
+            }
+        }
+    }
+}
+
+// the header up to end_header and the rest of the file
+fn parse_header(data: &[u8]) -> Result<(PlyFormat, Vec<Element>, &[u8]), MeshError> {
+    let mut rest = data;
+    let mut next_line = || {
+        let end = rest.iter().position(|&b| b == b'\n')?;
+        let line = String::from_utf8_lossy(&rest[..end]).trim().to_string();
+        rest = &rest[end + 1..];
+        Some(line)
+    };
+    if next_line().as_deref() != Some("ply") {
+        return Err(malformed("not a ply file".to_string()));
+    }
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    loop {
+        let line = next_line().ok_or_else(|| malformed("the header has no end_header".to_string()))?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            ["end_header"] => break,
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            ["format", f, "1.0"] => format = Some(match f {
+                "ascii" => PlyFormat::Ascii,
+                "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                _ => return Err(malformed(format!("unknown format `{}`", f))),
+            }),
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| malformed(format!("`{}` is not an element count", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", ..] => {
+                let element = elements.last_mut().ok_or_else(|| malformed("a property before any element".to_string()))?;
+                element.properties.push(match words[1..] {
+                    ["list", count, item, name] => Property::List(name.to_string(), Scalar::parse(count)?, Scalar::parse(item)?),
+                    [ty, name] => Property::Scalar(name.to_string(), Scalar::parse(ty)?),
+                    _ => return Err(malformed(format!("malformed property `{}`", line))),
+                });
+            }
+            _ => return Err(malformed(format!("malformed header line `{}`", line))),
+        }
+    }
+    let format = format.ok_or_else(|| malformed("the header has no format".to_string()))?;
+    Ok((format, elements, rest))
+}
+
+// vertex positions, colors (red green blue), normals (nx ny nz) and texture coordinates
+// (s t or u v) and faces (vertex_indices), polygons are fanned into triangles. other elements
+// and properties are skipped
+pub fn read_ply<R: Read>(mut reader: R) -> Result<Mesh, MeshError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let (format, elements, rest) = parse_header(&data)?;
+    let mut body = match format {
+        PlyFormat::Ascii => Body::Ascii(std::str::from_utf8(rest)
+            .map_err(|_| malformed("the ascii data is not text".to_string()))?.split_ascii_whitespace()),
+        _ => Body::Binary { data: rest, big_endian: format == PlyFormat::BinaryBigEndian },
+    };
+
+    let (mut positions, mut colors, mut normals, mut tex_coords, mut indices) = (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for element in &elements {
+        let find = |names: &[&str]| names.iter().map(|n| element.properties.iter().position(|p| matches!(p, Property::Scalar(s, _) if s == n))).collect::<Option<Vec<_>>>();
+        let (xyz, rgb, nxyz) = (find(&["x", "y", "z"]), find(&["red", "green", "blue"]), find(&["nx", "ny", "nz"]));
+        let st = find(&["s", "t"]).or_else(|| find(&["u", "v"]));
+        if element.name == "vertex" && xyz.is_none() {
+            return Err(malformed("vertices have no x, y and z".to_string()));
+        }
+        // every channel is scaled from the range of its type to 0..255, float ones are 0..1.
+        // anything outside is clamped
+        let color_max = rgb.as_ref().map(|c| c.iter().map(|&i| match &element.properties[i] {
+            Property::Scalar(_, ty) => ty.color_max(),
+            Property::List(..) => unreachable!(),
+        }).collect::<Vec<_>>());
+
+        for _ in 0..element.count {
+            let mut values = Vec::with_capacity(element.properties.len());
+            let mut face = Vec::new();
+            for p in &element.properties {
+                match p {
+                    Property::Scalar(_, ty) => values.push(body.read(*ty)?),
+                    Property::List(name, count, item) => {
+                        values.push(0.0);
+                        let n = body.read(*count)? as usize;
+                        let list = (0..n).map(|_| body.read(*item)).collect::<Result<Vec<_>, _>>()?;
+                        if name == "vertex_indices" || name == "vertex_index" {
+                            face = list;
+                        }
+                    }
+                }
+            }
+            let get = |i: &Option<Vec<usize>>| i.as_ref().map(|i| i.iter().map(|&i| values[i]).collect::<Vec<_>>());
+            match element.name.as_str() {
+                "vertex" => {
+                    let p = get(&xyz).unwrap();
+                    positions.push(V3d::new(p[0], p[1], p[2]));
+                    colors.push(get(&rgb).map_or(V3d::new(255.0, 255.0, 255.0), |c| {
+                        let max = color_max.as_ref().unwrap();
+                        V3d::from_fn(|i, _| (c[i] / max[i]).clamp(0.0, 1.0) * 255.0)
+                    }));
+                    if let Some(n) = get(&nxyz) {
+                        normals.push(V3d::new(n[0], n[1], n[2]));
+                    }
+                    if let Some(t) = get(&st) {
+                        tex_coords.push(Vector2::new(t[0], t[1]));
+                    }
+                }
+                "face" => {
+                    if face.len() < 3 {
+                        return Err(malformed(format!("a face needs at least 3 vertices, got {}", face.len())));
+                    }
+                    if face.iter().any(|&i| i < 0.0) {
+                        return Err(malformed("a face has a negative vertex index".to_string()));
+                    }
+                    let face: Vec<usize> = face.iter().map(|&i| i as usize).collect();
+                    for pair in face[1..].windows(2) {
+                        indices.push(Vector3::new(face[0], pair[0], pair[1]));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(&index) = indices.iter().flat_map(|t: &Vector3<usize>| t.iter()).find(|&&i| i >= positions.len()) {
+        return Err(MeshError::IndexOutOfRange { index, count: positions.len() });
+    }
+    Ok(Mesh { normals, tex_coords, ..Mesh::new(positions, colors, indices) })
+}
+
+pub fn load_ply<P: AsRef<Path>>(path: P) -> Result<Mesh, MeshError> {
+    read_ply(File::open(path)?)
+}
+
+enum Value {
+    U8(u8),
+    I32(i32),
+    F64(f64),
+}
+
+fn write_row<W: Write>(w: &mut W, format: PlyFormat, row: &[Value]) -> io::Result<()> {
+    if format == PlyFormat::Ascii {
+        let words: Vec<String> = row.iter().map(|v| match v {
+            Value::U8(x) => x.to_string(),
+            Value::I32(x) => x.to_string(),
+            Value::F64(x) => x.to_string(),
+        }).collect();
+        return writeln!(w, "{}", words.join(" "));
+    }
+    let big_endian = format == PlyFormat::BinaryBigEndian;
+    for v in row {
+        match v {
+            Value::U8(x) => w.write_all(&[*x])?,
+            Value::I32(x) => w.write_all(&if big_endian { x.to_be_bytes() } else { x.to_le_bytes() })?,
+            Value::F64(x) => w.write_all(&if big_endian { x.to_be_bytes() } else { x.to_le_bytes() })?,
+        }
+    }
+    Ok(())
+}
+
+// doubles for positions so they come back exactly, colors are rounded to uchar
+pub fn write_ply<W: Write>(mut w: W, mesh: &Mesh, format: PlyFormat) -> io::Result<()> {
+    let n = mesh.positions.len();
+    let has_normals = !mesh.normals.is_empty() && mesh.normals.len() == n;
+    let has_tex_coords = !mesh.tex_coords.is_empty() && mesh.tex_coords.len() == n;
+    let format_name = match format {
+        PlyFormat::Ascii => "ascii",
+        PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        PlyFormat::BinaryBigEndian => "binary_big_endian",
+    };
+    writeln!(w, "ply\nformat {} 1.0\ncomment games101", format_name)?;
+    writeln!(w, "element vertex {}", n)?;
+    writeln!(w, "property double x\nproperty double y\nproperty double z")?;
+    writeln!(w, "property uchar red\nproperty uchar green\nproperty uchar blue")?;
+    if has_normals {
+        writeln!(w, "property double nx\nproperty double ny\nproperty double nz")?;
+    }
+    if has_tex_coords {
+        writeln!(w, "property double s\nproperty double t")?;
+    }
+    writeln!(w, "element face {}\nproperty list uchar int vertex_indices\nend_header", mesh.indices.len())?;
+
+    for i in 0..n {
+        let (p, c) = (mesh.positions[i], mesh.colors.get(i).copied().unwrap_or(V3d::new(255.0, 255.0, 255.0)));
+        let mut row = vec![Value::F64(p.x), Value::F64(p.y), Value::F64(p.z)];
+        row.extend(c.iter().map(|c| Value::U8(c.round().clamp(0.0, 255.0) as u8)));
+        if has_normals {
+            row.extend(mesh.normals[i].iter().map(|&x| Value::F64(x)));
+        }
+        if has_tex_coords {
+            row.extend(mesh.tex_coords[i].iter().map(|&x| Value::F64(x)));
+        }
+        write_row(&mut w, format, &row)?;
+    }
+    for t in &mesh.indices {
+        write_row(&mut w, format, &[Value::U8(3), Value::I32(t.x as i32), Value::I32(t.y as i32), Value::I32(t.z as i32)])?;
+    }
+    w.flush()
+}
+
+pub fn save_ply<P: AsRef<Path>>(path: P, mesh: &Mesh, format: PlyFormat) -> io::Result<()> {
+    write_ply(BufWriter::new(File::create(path)?), mesh, format)
+}
@@ -0,0 +1,1208 @@
+use std::collections::HashMap;
+use crate::utils::{get_normal_matrix, min, max, V3d};
+use nalgebra::{Matrix4, Vector3, Vector4, Vector2};
+use crate::triangle::{interpolate, Triangle};
+use crate::camera::{Camera, DepthRange, Projection};
+use crate::clip::{clip_line, clip_line_to_rect, clip_polygon, frustum_planes, ClipVertex};
+use crate::msaa::{ResolveFilter, SamplePattern};
+use crate::fxaa::{self, FxaaSettings};
+use crate::taa::{self, TaaSettings};
+use crate::ssaa;
+use crate::line::{self, LineCap, LineMode, LineStyle};
+use crate::shader::{FragmentInput, FragmentShader, Varying, VertexOutput, VertexShader, WithDerivatives};
+use crate::lighting::{Lighting, Surface};
+use crate::texture::Texture;
+use crate::mesh::{Mesh, MeshBuffers};
+
+#[allow(dead_code)]
+pub enum Buffer {
+    Color,
+    Depth,
+    Both,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Primitive {
+    Point,
+    Line,
+    Triangle,
+    // filled triangles with their edges drawn on top
+    ShadedWireframe,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CullMode {
+    #[default]
+    None,
+    Back,
+    Front,
+}
+
+// the screen space winding of a front facing triangle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FrontFace {
+    #[default]
+    Ccw,
+    Cw,
+}
+
+// one antialiasing method at a time, to compare them on the same scene
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum AntiAliasing {
+    // one sample in the pixel center
+    #[default]
+    None,
+    // the whole pipeline at factor times the resolution, then filtered down
+    Ssaa { factor: u64, filter: ResolveFilter },
+    Msaa { samples: usize, pattern: SamplePattern, filter: ResolveFilter },
+    Fxaa(FxaaSettings),
+    Taa(TaaSettings),
+}
+
+// triangle counters since the last clear of the color buffer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub triangles: usize,
+    pub clipped: usize,
+    pub culled: usize,
+}
+
+#[derive(Default, Clone)]
+pub struct Rasterizer {
+    model: Matrix4<f64>,
+    view: Matrix4<f64>,
+    projection: Matrix4<f64>,
+    // near / far behind the projection matrix and the depth range the viewport maps them to
+    projection_params: Projection,
+    depth_range: Option<DepthRange>,
+    rotation: Matrix4<f64>,
+    // user clip planes in world space, see add_clip_plane
+    clip_planes: Vec<Vector4<f64>>,
+    cull_mode: CullMode,
+    front_face: FrontFace,
+    stats: Stats,
+    line_style: LineStyle,
+    pos_buf: HashMap<usize, Vec<Vector3<f64>>>,
+    ind_buf: HashMap<usize, Vec<Vector3<usize>>>,
+    col_buf: HashMap<usize, Vec<Vector3<f64>>>,
+    tex_buf: HashMap<usize, Vec<Vector2<f64>>>,
+    normal_buf: HashMap<usize, Vec<Vector3<f64>>>,
+    // lights and material for draw_lit, lights are in world space
+    lighting: Lighting,
+
+    frame_buf: Vec<Vector3<f64>>,
+    history_buf: Vec<Vector3<f64>>,
+    frame_buf_0: Vec<Vector3<f64>>,
+
+    depth_buf: Vec<f64>,
+    // MSAA sample positions inside a pixel, the sample buffers hold this many entries per pixel
+    samples: Vec<Vector2<f64>>,
+    sample_pattern: SamplePattern,
+    resolve_filter: ResolveFilter,
+    fxaa: Option<FxaaSettings>,
+    // width and height below are the render resolution, ssaa times the output
+    ssaa: u64,
+    ssaa_filter: ResolveFilter,
+    taa: Option<TaaSettings>,
+    // counts resolved frames, picks the TAA jitter
+    frame_index: u64,
+    history_valid: bool,
+    // maps the clip space of the current draw to where it was in the last frame
+    reprojection: Matrix4<f64>,
+    // unjittered mvp of every position buffer in the last and the current frame
+    prev_mvp: HashMap<usize, Matrix4<f64>>,
+    cur_mvp: HashMap<usize, Matrix4<f64>>,
+    frame_sample: Vec<Vector3<f64>>,
+    depth_sample: Vec<f64>,
+    // motion vectors in pixels, towards where the surface was in the last frame
+    velocity_sample: Vec<Vector2<f64>>,
+    velocity_buf: Vec<Vector2<f64>>,
+    width: u64,
+    height: u64,
+    next_id: usize,
+}
+
+#[derive(Clone, Copy)]
+pub struct PosBufId(usize);
+
+#[derive(Clone, Copy)]
+pub struct IndBufId(usize);
+
+#[derive(Clone, Copy)]
+pub struct ColBufId(usize);
+
+#[derive(Clone, Copy)]
+pub struct NormBufId(usize);
+
+#[derive(Clone, Copy)]
+pub struct TexBufId(usize);
+
+impl Rasterizer {
+    pub fn new(w: u64, h: u64) -> Self {
+        let mut r = Rasterizer {
+            width: w,
+            height: h,
+            ssaa: 1,
+            rotation: Matrix4::identity(),
+            reprojection: Matrix4::identity(),
+            ..Default::default()
+        };
+        r.projection = r.projection_params.matrix();
+        r.resize_buffers();
+        r.set_msaa(4, SamplePattern::OrderedGrid);
+        r
+    }
+
+    // reallocates every per pixel buffer for the current render resolution
+    fn resize_buffers(&mut self) {
+        let n = (self.width * self.height) as usize;
+        self.frame_buf = vec![Vector3::zeros(); n / (self.ssaa * self.ssaa) as usize];
+        self.frame_buf_0 = vec![Vector3::zeros(); n];
+        self.depth_buf = vec![self.depth_range().clear_value(); n];
+        self.history_buf = vec![Vector3::zeros(); n];
+        self.velocity_buf = vec![Vector2::zeros(); n];
+        self.history_valid = false;
+        self.resize_samples();
+    }
+
+    fn resize_samples(&mut self) {
+        let len = (self.width * self.height) as usize * self.samples.len();
+        self.frame_sample = vec![Vector3::zeros(); len];
+        self.depth_sample = vec![self.depth_range().clear_value(); len];
+        self.velocity_sample = vec![Vector2::zeros(); len];
+    }
+
+    // replaces whatever antialiasing was set up before
+    pub fn set_antialiasing(&mut self, mode: AntiAliasing) {
+        self.set_fxaa(None);
+        self.set_taa(None);
+        self.set_ssaa(1, ResolveFilter::Box);
+        self.set_resolve_filter(ResolveFilter::Box);
+        self.set_msaa(1, SamplePattern::OrderedGrid);
+        match mode {
+            AntiAliasing::None => {}
+            AntiAliasing::Ssaa { factor, filter } => self.set_ssaa(factor, filter),
+            AntiAliasing::Msaa { samples, pattern, filter } => {
+                self.set_msaa(samples, pattern);
+                self.set_resolve_filter(filter);
+            }
+            AntiAliasing::Fxaa(settings) => self.set_fxaa(Some(settings)),
+            AntiAliasing::Taa(settings) => self.set_taa(Some(settings)),
+        }
+    }
+
+    // renders at factor times the width and height and filters down to the output size in resolve()
+    pub fn set_ssaa(&mut self, factor: u64, filter: ResolveFilter) {
+        assert!(factor >= 1, "SSAA factor has to be at least 1");
+        let (w, h) = (self.width(), self.height());
+        self.ssaa = factor;
+        self.ssaa_filter = filter;
+        self.width = w * factor;
+        self.height = h * factor;
+        self.resize_buffers();
+    }
+
+    // 1, 2, 4, 8 or 16 samples per pixel, resizes and clears the sample buffers
+    pub fn set_msaa(&mut self, count: usize, pattern: SamplePattern) {
+        self.samples = pattern.offsets(count);
+        self.sample_pattern = pattern;
+        self.resize_samples();
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn sample_pattern(&self) -> &SamplePattern {
+        &self.sample_pattern
+    }
+
+    pub fn set_resolve_filter(&mut self, filter: ResolveFilter) {
+        self.resolve_filter = filter;
+    }
+
+    // None turns FXAA off
+    pub fn set_fxaa(&mut self, settings: Option<FxaaSettings>) {
+        self.fxaa = settings;
+    }
+
+    fn get_index1(&self, x: usize, y: usize) -> usize {
+        ((self.height - 1 - y as u64) * self.width + x as u64) as usize
+    }
+
+    // the samples of a pixel are stored next to each other
+    fn get_sample_index(&self, x: usize, y: usize, sample: usize) -> usize {
+        self.get_index1(x, y) * self.samples.len() + sample
+    }
+
+    // Bresenham in screen space, begin and end carry the screen space depth in z
+    fn draw_line(&mut self, begin: &V3d, end: &V3d, colors: &[V3d; 2]) {
+        // only the part on screen is stepped, depth and color are cut along with it
+        let hi = Vector2::new(self.width as f64 - 1.0, self.height as f64 - 1.0);
+        let Some((t0, t1)) = clip_line_to_rect(begin.xy(), end.xy(), Vector2::zeros(), hi) else {
+            return;
+        };
+        let (begin, end) = (begin + (end - begin) * t0, begin + (end - begin) * t1);
+        let colors = [colors[0] + (colors[1] - colors[0]) * t0, colors[0] + (colors[1] - colors[0]) * t1];
+        let (begin, end, colors) = (&begin, &end, &colors);
+
+        let (x1, y1) = (begin.x, begin.y);
+        let (x2, y2) = (end.x, end.y);
+
+        let dx = x2 - x1;
+        let dy = y2 - y1;
+        let dx1 = dx.abs();
+        let dy1 = dy.abs();
+        let mut px = 2.0 * dy1 - dx1;
+        let mut py = 2.0 * dx1 - dy1;
+
+        if dy1 <= dx1 {
+            let (mut x, mut y, xe) = if dx >= 0.0 {
+                (x1, y1, x2)
+            } else {
+                (x2, y2, x1)
+            };
+            self.line_pixel(x, y, begin, end, colors);
+            while x < xe {
+                x += 1.0;
+                if px < 0.0 {
+                    px += 2.0 * dy1;
+                } else {
+                    if (dx < 0.0 && dy < 0.0) || (dx > 0.0 && dy > 0.0) {
+                        y += 1.0;
+                    } else { y -= 1.0; }
+                    px += 2.0 * (dy1 - dx1);
+                }
+                self.line_pixel(x, y, begin, end, colors);
+            }
+        } else {
+            let (mut x, mut y, ye) = if dy >= 0.0 {
+                (x1, y1, y2)
+            } else {
+                (x2, y2, y1)
+            };
+            self.line_pixel(x, y, begin, end, colors);
+            while y < ye {
+                y += 1.0;
+                if py < 0.0 {
+                    py += 2.0 * dx1;
+                } else {
+                    if (dx < 0.0 && dy < 0.0) || (dx > 0.0 && dy > 0.0) {
+                        x += 1.0;
+                    } else { x -= 1.0; }
+                    py += 2.0 * (dx1 - dy1);
+                }
+                self.line_pixel(x, y, begin, end, colors);
+            }
+        }
+    }
+
+    // one pixel of a line, depth is affine in screen space so both it and the color are lerped
+    fn line_pixel(&mut self, x: f64, y: f64, begin: &V3d, end: &V3d, colors: &[V3d; 2]) {
+        let (dx, dy) = (end.x - begin.x, end.y - begin.y);
+        let s = if dx.abs() >= dy.abs() {
+            if dx == 0.0 { 0.0 } else { (x - begin.x) / dx }
+        } else {
+            (y - begin.y) / dy
+        }.clamp(0.0, 1.0);
+        let depth = begin.z + (end.z - begin.z) * s;
+        let color = colors[0] + (colors[1] - colors[0]) * s;
+        self.plot(x.round(), y.round(), depth, &color);
+    }
+
+    // depth tested write of a point or line pixel into every sample of the pixel
+    fn plot(&mut self, x: f64, y: f64, depth: f64, color: &Vector3<f64>) {
+        if x < 0.0 || x >= self.width as f64 || y < 0.0 || y >= self.height as f64 {
+            return;
+        }
+        self.blend_pixel(x as usize, y as usize, depth, color, 1.0);
+    }
+
+    // mixes alpha of the color into the samples that pass the depth test, only a pixel that
+    // is mostly covered writes its depth
+    fn blend_pixel(&mut self, x: usize, y: usize, depth: f64, color: &Vector3<f64>, alpha: f64) {
+        let range = self.depth_range();
+        let passes = |stored: f64| range.closer(depth, stored);
+
+        for sample in 0..self.samples.len() {
+            let ind = self.get_sample_index(x, y, sample);
+            if passes(self.depth_sample[ind]) {
+                let old = self.frame_sample[ind];
+                self.frame_sample[ind] = old + (color - old) * alpha;
+                if alpha >= 0.5 {
+                    self.depth_sample[ind] = depth;
+                    self.velocity_sample[ind] = Vector2::zeros();
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self, buff: Buffer) {
+        match buff {
+            Buffer::Color => {
+                self.clear_color();
+            }
+            Buffer::Depth => {
+                self.clear_depth();
+            }
+            Buffer::Both => {
+                self.clear_color();
+                self.clear_depth();
+            }
+        }
+    }
+
+    fn clear_color(&mut self) {
+        self.frame_buf.fill(Vector3::new(0.0, 0.0, 0.0));
+        self.frame_buf_0.fill(Vector3::new(0.0, 0.0, 0.0));
+        self.frame_sample.fill(Vector3::new(0.0, 0.0, 0.0));
+        self.velocity_sample.fill(Vector2::zeros());
+        self.stats = Stats::default();
+    }
+
+    fn clear_depth(&mut self) {
+        let far = self.depth_range().clear_value();
+        self.depth_buf.fill(far);
+        self.depth_sample.fill(far);
+    }
+
+    pub fn set_model(&mut self, model: Matrix4<f64>) {
+        self.model = model;
+    }
+
+    pub fn set_view(&mut self, view: Matrix4<f64>) {
+        self.view = view;
+    }
+
+    // a raw matrix, the depth range still follows the last set_projection_params
+    pub fn set_projection(&mut self, projection: Matrix4<f64>) {
+        self.projection = projection;
+    }
+
+    pub fn set_projection_params(&mut self, params: Projection) {
+        self.projection = params.matrix();
+        self.projection_params = params;
+    }
+
+    // the camera's view and projection, its aspect ratio is used as it is
+    pub fn set_camera(&mut self, camera: &Camera) {
+        self.set_view(camera.view_matrix());
+        self.set_projection_params(camera.projection());
+    }
+
+    pub fn projection_params(&self) -> Projection {
+        self.projection_params
+    }
+
+    // overrides the default [z_near, z_far] mapping, e.g. DepthRange::unit() or DepthRange::reverse_z().
+    // depths stored under the old range do not compare with the new one, so the depth buffers are cleared
+    pub fn set_depth_range(&mut self, range: DepthRange) {
+        self.depth_range = Some(range);
+        self.clear_depth();
+    }
+
+    pub fn depth_range(&self) -> DepthRange {
+        self.depth_range.unwrap_or(self.projection_params.depth_range())
+    }
+
+    pub fn set_rotation(&mut self, rotation: Matrix4<f64>) {
+        self.rotation = rotation;
+    }
+
+    // the transform draw() applies, for vertex shaders that want to do the same
+    pub fn mvp(&self) -> Matrix4<f64> {
+        self.projection * self.view * self.model * self.rotation
+    }
+
+    // None turns TAA off, either way the history starts over
+    pub fn set_taa(&mut self, settings: Option<TaaSettings>) {
+        self.taa = settings;
+        self.reset_history();
+    }
+
+    // for cuts, the next frame does not blend with what came before
+    pub fn reset_history(&mut self) {
+        self.history_valid = false;
+        self.prev_mvp.clear();
+        self.cur_mvp.clear();
+    }
+
+    // subpixel offset of the current frame in pixels, zero without TAA
+    pub fn jitter(&self) -> Vector2<f64> {
+        match &self.taa {
+            Some(settings) => taa::jitter_offset(self.frame_index, settings.jitter_samples),
+            None => Vector2::zeros(),
+        }
+    }
+
+    // moves NDC so the image shifts by jitter() pixels
+    fn jitter_matrix(&self) -> Matrix4<f64> {
+        let j = self.jitter();
+        let mut m = Matrix4::identity();
+        m[(0, 3)] = 2.0 * j.x / self.width as f64;
+        m[(1, 3)] = 2.0 * j.y / self.height as f64;
+        m
+    }
+
+    // keeps the world space points with plane.dot((x, y, z, 1)) >= 0
+    pub fn add_clip_plane(&mut self, plane: Vector4<f64>) {
+        self.clip_planes.push(plane);
+    }
+
+    pub fn clear_clip_planes(&mut self) {
+        self.clip_planes.clear();
+    }
+
+    pub fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        self.cull_mode = cull_mode;
+    }
+
+    pub fn set_front_face(&mut self, front_face: FrontFace) {
+        self.front_face = front_face;
+    }
+
+    // how lines, wireframes and overlay edges are drawn
+    pub fn set_line_style(&mut self, style: LineStyle) {
+        assert!(style.width > 0.0, "line width has to be positive");
+        self.line_style = style;
+    }
+
+    pub fn line_style(&self) -> LineStyle {
+        self.line_style
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    fn get_next_id(&mut self) -> usize {
+        let res = self.next_id;
+        self.next_id += 1;
+        res
+    }
+
+    pub fn load_position(&mut self, positions: &[Vector3<f64>]) -> PosBufId {
+        let id = self.get_next_id();
+        self.pos_buf.insert(id, positions.to_vec());
+        PosBufId(id)
+    }
+
+    pub fn load_indices(&mut self, indices: &[Vector3<usize>]) -> IndBufId {
+        let id = self.get_next_id();
+        self.ind_buf.insert(id, indices.to_vec());
+        IndBufId(id)
+    }
+
+    pub fn load_colors(&mut self, colors: &[Vector3<f64>]) -> ColBufId {
+        let id = self.get_next_id();
+        self.col_buf.insert(id, colors.to_vec());
+        ColBufId(id)
+    }
+
+    pub fn load_normals(&mut self, normals: &[Vector3<f64>]) -> NormBufId {
+        let id = self.get_next_id();
+        self.normal_buf.insert(id, normals.to_vec());
+        NormBufId(id)
+    }
+
+    pub fn load_tex_coords(&mut self, tex_coords: &[Vector2<f64>]) -> TexBufId {
+        let id = self.get_next_id();
+        self.tex_buf.insert(id, tex_coords.to_vec());
+        TexBufId(id)
+    }
+
+    // every list of the mesh in its own buffer, normals and texture coordinates only if it has them
+    pub fn load_mesh(&mut self, mesh: &Mesh) -> MeshBuffers {
+        MeshBuffers {
+            position: self.load_position(&mesh.positions),
+            indices: self.load_indices(&mesh.indices),
+            colors: self.load_colors(&mesh.colors),
+            normals: (!mesh.normals.is_empty()).then(|| self.load_normals(&mesh.normals)),
+            tex_coords: (!mesh.tex_coords.is_empty()).then(|| self.load_tex_coords(&mesh.tex_coords)),
+            groups: mesh.groups.iter().map(|g| self.load_indices(&mesh.indices[g.triangles.clone()])).collect(),
+        }
+    }
+
+    // the buffers back as a mesh, e.g. to save what a scene draws
+    pub fn mesh(&self, pos_buffer: PosBufId, ind_buffer: IndBufId, col_buffer: ColBufId) -> Mesh {
+        Mesh::new(self.pos_buf[&pos_buffer.0].clone(), self.col_buf[&col_buffer.0].clone(), self.ind_buf[&ind_buffer.0].clone())
+    }
+
+    // filled triangles colored by the texture, trilinear filtering uses the screen space
+    // derivatives of the texture coordinates
+    pub fn draw_textured(&mut self, pos_buffer: PosBufId, ind_buffer: IndBufId, tex_buffer: TexBufId, texture: &Texture) {
+        let buf = self.pos_buf[&pos_buffer.0].clone();
+        let ind = self.ind_buf[&ind_buffer.0].clone();
+        let uv = self.tex_buf[&tex_buffer.0].clone();
+        let mvp = self.mvp();
+        let reprojection = self.get_reprojection(pos_buffer, mvp);
+
+        let vertices: Vec<usize> = (0..buf.len()).collect();
+        let vs = |&i: &usize| VertexOutput { position: mvp * to_vec4(buf[i], Some(1.0)), varying: uv[i] };
+        let fs = WithDerivatives(|f: &FragmentInput<Vector2<f64>>| Some(texture.sample_grad(f.varying, f.ddx, f.ddy)));
+        self.shade_triangles(&vertices, &ind, &vs, &fs, reprojection);
+    }
+
+    pub fn set_lighting(&mut self, lighting: Lighting) {
+        self.lighting = lighting;
+    }
+
+    pub fn lighting(&self) -> &Lighting {
+        &self.lighting
+    }
+
+    // filled triangles lit with Blinn-Phong, the colors are the diffuse color and the normals are
+    // in model space, they go through the normal matrix of the model view transform
+    pub fn draw_lit(&mut self, pos_buffer: PosBufId, ind_buffer: IndBufId, col_buffer: ColBufId, normal_buffer: NormBufId) {
+        let buf = self.pos_buf[&pos_buffer.0].clone();
+        let ind = self.ind_buf[&ind_buffer.0].clone();
+        let col = self.col_buf[&col_buffer.0].clone();
+        let normals = self.normal_buf[&normal_buffer.0].clone();
+
+        let model_view = self.view * self.model * self.rotation;
+        let normal_matrix = get_normal_matrix(&model_view);
+        let mvp = self.mvp();
+        let reprojection = self.get_reprojection(pos_buffer, mvp);
+        // shading happens in view space where the camera is at the origin
+        let lighting = self.lighting.to_view(&self.view);
+
+        let vertices: Vec<usize> = (0..buf.len()).collect();
+        let vs = |&i: &usize| {
+            let p = to_vec4(buf[i], Some(1.0));
+            VertexOutput {
+                position: mvp * p,
+                varying: Surface { position: (model_view * p).xyz(), normal: normal_matrix * normals[i], color: col[i] },
+            }
+        };
+        let fs = |f: &FragmentInput<Surface>| Some(lighting.shade(&f.varying));
+        self.shade_triangles(&vertices, &ind, &vs, &fs, reprojection);
+    }
+
+    // without a color buffer, as in LAB1, lines and points are drawn in the line style's color
+    pub fn draw_triangle(&mut self, pos_buffer: PosBufId, ind_buffer: IndBufId, typ: Primitive) {
+        let buf = self.pos_buf[&pos_buffer.0].clone();
+        let ind = self.ind_buf[&ind_buffer.0].clone();
+        self.draw_primitives(pos_buffer, &buf, &ind, None, typ);
+    }
+
+    // the fixed pipeline interpolates the vertex colors and depth, normals and texture coordinates
+    // are drawn with draw_lit, draw_textured or draw_shaded
+    pub fn draw(&mut self, pos_buffer: PosBufId, ind_buffer: IndBufId, col_buffer: ColBufId, typ: Primitive) {
+        let buf = self.pos_buf[&pos_buffer.0].clone();
+        let ind = self.ind_buf[&ind_buffer.0].clone();
+        let col = self.col_buf[&col_buffer.0].clone();
+        self.draw_primitives(pos_buffer, &buf, &ind, Some(&col), typ);
+    }
+
+    fn draw_primitives(&mut self, pos_buffer: PosBufId, buf: &[V3d], ind: &[Vector3<usize>], col: Option<&[V3d]>, typ: Primitive) {
+        let mvp = self.mvp();
+        self.reprojection = self.get_reprojection(pos_buffer, mvp);
+        let mvp = self.jitter_matrix() * mvp;
+        let planes = self.get_clip_planes();
+
+        // the overlay edges are drawn once all triangles are filled so no fill covers them
+        let mut overlay = vec![];
+        for i in ind {
+            let mut t = self.get_triangle(buf, mvp, i);
+            match col {
+                Some(col) => {
+                    for (j, c) in [col[i[0]], col[i[1]], col[i[2]]].iter().enumerate() {
+                        t.set_color(j, c[0], c[1], c[2]);
+                    }
+                }
+                None => {
+                    let c = self.line_style.color;
+                    for j in 0..3 {
+                        t.set_color(j, c.x, c.y, c.z);
+                    }
+                }
+            }
+
+            let triangles = self.clip_triangle(&t, &planes);
+            let fan: Vec<[Vector4<f64>; 3]> = triangles.iter().map(|t| t.v).collect();
+            if !self.count_triangle(&fan) {
+                continue;
+            }
+            match typ {
+                Primitive::Point => self.draw_points(&t, &planes),
+                Primitive::Line => self.draw_edges(&t, &planes, None, depth_slope(&triangles)),
+                Primitive::Triangle | Primitive::ShadedWireframe => {
+                    for t in &triangles {
+                        self.rasterize_triangle(t);
+                    }
+                    if typ == Primitive::ShadedWireframe {
+                        overlay.push((t, depth_slope(&triangles)));
+                    }
+                }
+            }
+        }
+        let color = self.line_style.color;
+        for (t, slope) in &overlay {
+            self.draw_edges(t, &planes, Some(color), *slope);
+        }
+    }
+
+    // the vertices of a clip space triangle that are inside every plane
+    fn draw_points(&mut self, t: &Triangle, planes: &[Vector4<f64>]) {
+        for j in 0..3 {
+            if planes.iter().all(|p| p.dot(&t.v[j]) >= 0.0) {
+                let v = self.viewport(&t.v[j]);
+                self.plot(v.x.floor(), v.y.floor(), v.z, &(t.color[j] * 255.0));
+            }
+        }
+    }
+
+    // the edges of a clip space triangle, in the vertex colors unless a color is given, slope is
+    // the steepest depth change per pixel of the filled triangle for the depth bias
+    fn draw_edges(&mut self, t: &Triangle, planes: &[Vector4<f64>], color: Option<V3d>, slope: f64) {
+        let verts: Vec<ClipVertex> = (0..3).map(|j| ClipVertex::from_triangle(t, j)).collect();
+        let bias = self.line_depth_bias(slope);
+        self.draw_clipped_strip(&verts, true, planes, color, bias);
+    }
+
+    // added to the depth of a line to move it towards the camera
+    fn line_depth_bias(&self, slope: f64) -> f64 {
+        let range = self.depth_range();
+        let style = &self.line_style;
+        (range.near - range.far).signum() * (style.depth_bias * (range.far - range.near).abs() + style.slope_bias * slope)
+    }
+
+    // a polyline through world space points in the line style, closed joins the last point to the first
+    pub fn draw_line_strip(&mut self, points: &[V3d], closed: bool) {
+        let mvp = self.jitter_matrix() * self.mvp();
+        let planes = self.get_clip_planes();
+        let verts: Vec<ClipVertex> = points.iter().map(|p| ClipVertex {
+            pos: mvp * to_vec4(*p, Some(1.0)),
+            color: Vector3::zeros(),
+        }).collect();
+        let bias = self.line_depth_bias(0.0);
+        self.draw_clipped_strip(&verts, closed, planes.as_slice(), Some(self.line_style.color), bias);
+    }
+
+    // every segment is clipped on its own so the cuts do not show up as extra lines, where one
+    // is cut the strip is split and the pieces get end caps instead of joins
+    fn draw_clipped_strip(&mut self, verts: &[ClipVertex], closed: bool, planes: &[Vector4<f64>],
+                          color: Option<V3d>, bias: f64) {
+        let n = verts.len();
+        if n < 2 {
+            return;
+        }
+        let segments = if closed { n } else { n - 1 };
+        let mut pieces: Vec<Vec<ClipVertex>> = vec![];
+        let mut piece: Vec<ClipVertex> = vec![];
+        let mut whole = true;
+        for i in 0..segments {
+            let (a, b) = (&verts[i], &verts[(i + 1) % n]);
+            let Some((ca, cb)) = clip_line(a, b, planes) else {
+                whole = false;
+                pieces.extend((!piece.is_empty()).then(|| std::mem::take(&mut piece)));
+                continue;
+            };
+            if ca.pos != a.pos || piece.is_empty() {
+                whole &= ca.pos == a.pos;
+                pieces.extend((!piece.is_empty()).then(|| std::mem::take(&mut piece)));
+                piece.push(ca);
+            }
+            piece.push(cb);
+            if cb.pos != b.pos {
+                whole = false;
+                pieces.push(std::mem::take(&mut piece));
+            }
+        }
+        pieces.extend((!piece.is_empty()).then_some(piece));
+
+        let loop_closed = closed && whole && pieces.len() == 1;
+        if loop_closed {
+            pieces[0].pop();
+        } else if closed && pieces.len() > 1 && pieces[0][0].pos == verts[0].pos
+            && pieces[pieces.len() - 1].last().map(|v| v.pos) == Some(verts[0].pos) {
+            // the loop was cut somewhere else, its start and end still meet at the first point
+            let first = pieces.remove(0);
+            pieces.last_mut().unwrap().extend(first.into_iter().skip(1));
+        }
+
+        for piece in pieces {
+            let points: Vec<V3d> = piece.iter().map(|v| self.viewport(&v.pos).xyz() + Vector3::new(0.0, 0.0, bias)).collect();
+            let colors: Vec<V3d> = piece.iter().map(|v| color.unwrap_or(v.color * 255.0)).collect();
+            self.draw_polyline(&points, &colors, loop_closed);
+        }
+    }
+
+    // screen space polyline with the depth in z, one pixel Bresenham lines are stepped, anything
+    // else is drawn from the coverage of each pixel by the segments, caps and joins
+    fn draw_polyline(&mut self, points: &[V3d], colors: &[V3d], closed: bool) {
+        let style = self.line_style;
+        // the width is in output pixels, SSAA renders it at factor times that
+        let width = style.width * self.ssaa as f64;
+        let n = points.len();
+        let segments = if closed { n } else { n - 1 };
+        if style.mode == LineMode::Bresenham && width <= 1.0 {
+            for i in 0..segments {
+                let j = (i + 1) % n;
+                self.draw_line(&points[i], &points[j], &[colors[i], colors[j]]);
+            }
+            return;
+        }
+
+        let half_width = width / 2.0;
+        let reach = line::reach(&LineStyle { width, ..style });
+        let xy = |p: &V3d| Vector2::new(p.x, p.y);
+        // where shapes of the strip overlap the pixel keeps the largest coverage, so nothing is blended twice
+        let mut coverage: HashMap<(usize, usize), (f64, f64, V3d)> = HashMap::new();
+        let mut cover = |x: usize, y: usize, c: f64, depth: f64, color: V3d| {
+            let entry = coverage.entry((x, y)).or_insert((0.0, depth, color));
+            if c > entry.0 {
+                *entry = (c, depth, color);
+            }
+        };
+
+        for i in 0..segments {
+            let j = (i + 1) % n;
+            let start = if closed || i > 0 { LineCap::Butt } else { style.cap };
+            let end = if closed || j != n - 1 { LineCap::Butt } else { style.cap };
+            let (a, b) = (xy(&points[i]), xy(&points[j]));
+            // the box only has to cover the part of the segment within reach of the screen
+            let screen = Vector2::new(self.width as f64, self.height as f64).add_scalar(reach);
+            let Some((t0, t1)) = clip_line_to_rect(a, b, Vector2::repeat(-reach), screen) else {
+                continue;
+            };
+            let (ca, cb) = (a + (b - a) * t0, a + (b - a) * t1);
+            for (x, y) in self.pixels_along(ca, cb, reach) {
+                let p = Vector2::new(x as f64 + 0.5, y as f64 + 0.5);
+                let (c, t) = line::segment_coverage(p, a, b, half_width, start, end);
+                if c > 0.0 {
+                    let depth = points[i].z + (points[j].z - points[i].z) * t;
+                    cover(x, y, c, depth, colors[i] + (colors[j] - colors[i]) * t);
+                }
+            }
+        }
+
+        let corners = if closed { 0..n } else { 1..n.saturating_sub(1) };
+        for i in corners {
+            let (prev, next) = ((i + n - 1) % n, (i + 1) % n);
+            let v = xy(&points[i]);
+            let (d_in, d_out) = (v - xy(&points[prev]), xy(&points[next]) - v);
+            if d_in.norm() < 1e-9 || d_out.norm() < 1e-9 {
+                continue;
+            }
+            let (d_in, d_out) = (d_in.normalize(), d_out.normalize());
+            for (x, y) in self.pixels_around(v, v, reach) {
+                let p = Vector2::new(x as f64 + 0.5, y as f64 + 0.5);
+                let c = line::join_coverage(p, v, d_in, d_out, half_width, style.join, style.miter_limit);
+                if c > 0.0 {
+                    cover(x, y, c, points[i].z, colors[i]);
+                }
+            }
+        }
+
+        for ((x, y), (c, depth, color)) in coverage {
+            let alpha = match style.mode {
+                LineMode::Antialiased => c,
+                // hard edges, a pixel is in when its center is
+                LineMode::Bresenham => if c >= 0.5 { 1.0 } else { continue },
+            };
+            self.blend_pixel(x, y, depth, &color, alpha);
+        }
+    }
+
+    // the pixels within reach of the box from lo to hi, clamped to the screen
+    fn pixels_around(&self, lo: Vector2<f64>, hi: Vector2<f64>, reach: f64) -> impl Iterator<Item = (usize, usize)> {
+        let x0 = (lo.x - reach).floor().max(0.0) as usize;
+        let y0 = (lo.y - reach).floor().max(0.0) as usize;
+        let x1 = (hi.x + reach).ceil().min(self.width as f64 - 1.0);
+        let y1 = (hi.y + reach).ceil().min(self.height as f64 - 1.0);
+        // an empty range when the box is off screen
+        let (x1, y1) = if x1 < 0.0 || y1 < 0.0 { (0, 0) } else { (x1 as usize + 1, y1 as usize + 1) };
+        (y0..y1).flat_map(move |y| (x0..x1).map(move |x| (x, y)))
+    }
+
+    // the pixels whose centers are within reach of the segment a-b, a span of x for every row, so a
+    // diagonal segment costs its length rather than its bounding box
+    fn pixels_along(&self, a: Vector2<f64>, b: Vector2<f64>, reach: f64) -> impl Iterator<Item = (usize, usize)> {
+        let len = (b - a).norm();
+        let d = if len > 1e-9 { (b - a) / len } else { Vector2::new(1.0, 0.0) };
+        let n = Vector2::new(-d.y, d.x);
+        let (width, height) = (self.width as f64, self.height as f64);
+        let y0 = (a.y.min(b.y) - reach).floor().max(0.0) as usize;
+        let y1 = (a.y.max(b.y) + reach).ceil().clamp(0.0, height) as usize;
+        (y0..y1).flat_map(move |y| {
+            let py = y as f64 + 0.5;
+            // along the segment in [-reach, len + reach] and across it in [-reach, reach]
+            let (mut lo, mut hi) = (f64::NEG_INFINITY, f64::INFINITY);
+            for (axis, min, max) in [(d, -reach, len + reach), (n, -reach, reach)] {
+                // axis.x * x + k lies in [min, max]
+                let k = axis.y * (py - a.y) - axis.x * a.x;
+                if axis.x.abs() < 1e-12 {
+                    if k < min || k > max {
+                        hi = f64::NEG_INFINITY;
+                    }
+                    continue;
+                }
+                let (x0, x1) = ((min - k) / axis.x, (max - k) / axis.x);
+                lo = lo.max(x0.min(x1));
+                hi = hi.min(x0.max(x1));
+            }
+            // the pixel centers inside [lo, hi]
+            let x0 = (lo - 0.5).ceil().max(0.0);
+            let x1 = (hi - 0.5).floor().min(width - 1.0);
+            let xs = if x0 <= x1 { x0 as usize..x1 as usize + 1 } else { 0..0 };
+            xs.map(move |x| (x, y))
+        })
+    }
+
+    // the triangle stays in homogeneous clip space, clip_triangle does the divide. the colors are
+    // set by draw_primitives
+    fn get_triangle(&self, buf: &[V3d], mvp: Matrix4<f64>, i: &Vector3<usize>) -> Triangle {
+        let mut t = Triangle::new();
+        let v =
+            [mvp * to_vec4(buf[i[0]], Some(1.0)), // homogeneous coordinates
+             mvp * to_vec4(buf[i[1]], Some(1.0)),
+             mvp * to_vec4(buf[i[2]], Some(1.0))];
+
+        for (j, vert) in v.iter().enumerate() {
+            t.set_vertex(j, *vert);
+        }
+        t
+    }
+
+    // remembers the mvp of this buffer for the next frame and returns how to get from it to the last one
+    fn get_reprojection(&mut self, pos_buffer: PosBufId, mvp: Matrix4<f64>) -> Matrix4<f64> {
+        if self.taa.is_none() {
+            return Matrix4::identity();
+        }
+        self.cur_mvp.insert(pos_buffer.0, mvp);
+        match (self.prev_mvp.get(&pos_buffer.0), mvp.try_inverse()) {
+            (Some(prev), Some(inv)) => prev * inv,
+            _ => Matrix4::identity(),
+        }
+    }
+
+    // frustum planes plus the user planes moved from world into clip space
+    fn get_clip_planes(&self) -> Vec<Vector4<f64>> {
+        let mut planes = frustum_planes().to_vec();
+        if let Some(inv) = (self.jitter_matrix() * self.projection * self.view).try_inverse() {
+            planes.extend(self.clip_planes.iter().map(|p| inv.transpose() * p));
+        }
+        planes
+    }
+
+    // clips a clip space triangle and returns the fan of screen space triangles that is left
+    fn clip_triangle(&self, t: &Triangle, planes: &[Vector4<f64>]) -> Vec<Triangle> {
+        let polygon = clip_polygon((0..3).map(|j| ClipVertex::from_triangle(t, j)).collect(), planes);
+
+        let mut triangles = vec![];
+        for i in 1..polygon.len().saturating_sub(1) {
+            let mut t = Triangle::new();
+            for (j, vert) in [polygon[0], polygon[i], polygon[i + 1]].iter().enumerate() {
+                t.set_vertex(j, self.viewport(&vert.pos));
+                t.color[j] = vert.color;
+            }
+            triangles.push(t);
+        }
+        triangles
+    }
+
+    // updates the stats for one input triangle given the screen space fan left after clipping,
+    // false if it is not drawn
+    fn count_triangle(&mut self, fan: &[[Vector4<f64>; 3]]) -> bool {
+        self.stats.triangles += 1;
+        if fan.is_empty() {
+            self.stats.clipped += 1;
+            return false;
+        }
+        if self.is_culled(fan) {
+            self.stats.culled += 1;
+            return false;
+        }
+        true
+    }
+
+    fn is_front(&self, area: f64) -> bool {
+        (area > 0.0) == (self.front_face == FrontFace::Ccw)
+    }
+
+    // runs after the viewport transform, the fan from clipping all has the winding of the input
+    fn is_culled(&self, fan: &[[Vector4<f64>; 3]]) -> bool {
+        let front = self.is_front(fan.iter().map(signed_area).sum());
+        match self.cull_mode {
+            CullMode::None => false,
+            CullMode::Back => !front,
+            CullMode::Front => front,
+        }
+    }
+
+    // perspective divide and viewport transform, w is kept for perspective correct interpolation
+    fn viewport(&self, clip: &Vector4<f64>) -> Vector4<f64> {
+        let ndc = clip / clip.w;
+        Vector4::new(0.5 * self.width as f64 * (ndc.x + 1.0),
+                     0.5 * self.height as f64 * (ndc.y + 1.0),
+                     self.depth_range().map(ndc.z),
+                     clip.w)
+    }
+
+    // bounding box of the triangle clamped to the screen
+    fn get_aabb(&self, v: &[Vector4<f64>; 3]) -> (i32, i32, i32, i32) {
+        let w = self.width as f64 - 1.0;
+        let h = self.height as f64 - 1.0;
+        let x_min = max(0.0, min(w, min(v[0].x, min(v[1].x, v[2].x)))) as i32;
+        let x_max = max(0.0, min(w, max(v[0].x, max(v[1].x, v[2].x)))) as i32;
+        let y_min = max(0.0, min(h, min(v[0].y, min(v[1].y, v[2].y)))) as i32;
+        let y_max = max(0.0, min(h, max(v[0].y, max(v[1].y, v[2].y)))) as i32;
+        (x_min, x_max, y_min, y_max)
+    }
+
+    // writes the samples only, the pixels are filled in by resolve()
+    pub fn rasterize_triangle(&mut self, t: &Triangle) {
+        // the fixed pipeline is a fragment shader that returns the interpolated vertex color
+        let colors = [t.color[0] * 255.0, t.color[1] * 255.0, t.color[2] * 255.0];
+        self.rasterize_shaded(&t.v, &colors, &|f: &FragmentInput<V3d>| Some(f.varying));
+    }
+
+    // the programmable pipeline: vs runs once for every vertex, the triangles of the indices are
+    // clipped, culled and counted as in draw() and fs runs for every covered sample
+    pub fn draw_shaded<In, V: Varying>(&mut self, vertices: &[In], indices: &[Vector3<usize>],
+                                       vs: &impl VertexShader<In, V>, fs: &impl FragmentShader<V>) {
+        // the shader owns the transform, so there is no last frame to reproject to and TAA takes it as static
+        self.shade_triangles(vertices, indices, vs, fs, Matrix4::identity());
+    }
+
+    fn shade_triangles<In, V: Varying>(&mut self, vertices: &[In], indices: &[Vector3<usize>], vs: &impl VertexShader<In, V>,
+                                       fs: &impl FragmentShader<V>, reprojection: Matrix4<f64>) {
+        self.reprojection = reprojection;
+        let jitter = self.jitter_matrix();
+        let planes = self.get_clip_planes();
+        let out: Vec<VertexOutput<V>> = vertices.iter().map(|v| {
+            let o = vs.shade(v);
+            VertexOutput { position: jitter * o.position, ..o }
+        }).collect();
+
+        for i in indices {
+            let polygon = clip_polygon(vec![out[i[0]], out[i[1]], out[i[2]]], &planes);
+            let fan: Vec<([Vector4<f64>; 3], [V; 3])> = (1..polygon.len().saturating_sub(1)).map(|k| {
+                let v = [polygon[0], polygon[k], polygon[k + 1]];
+                (v.map(|o| self.viewport(&o.position)), v.map(|o| o.varying))
+            }).collect();
+            let positions: Vec<[Vector4<f64>; 3]> = fan.iter().map(|(v, _)| *v).collect();
+            if !self.count_triangle(&positions) {
+                continue;
+            }
+            for (v, varying) in &fan {
+                self.rasterize_shaded(v, varying, fs);
+            }
+        }
+    }
+
+    // v is in screen space with the clip space w, every sample that is inside and passes the
+    // depth test is shaded, the depth is only written when fs keeps the sample
+    fn rasterize_shaded<V: Varying>(&mut self, v: &[Vector4<f64>; 3], varying: &[V; 3], fs: &impl FragmentShader<V>) {
+        let front_facing = self.is_front(signed_area(v));
+        let w = [v[0].w, v[1].w, v[2].w];
+        let range = self.depth_range();
+        let (x_min, x_max, y_min, y_max) = self.get_aabb(v);
+        let at = |x: f64, y: f64| {
+            let (alpha, beta, gamma) = compute_barycentric2d(x, y, v);
+            interpolate(alpha, beta, gamma, varying, &w)
+        };
+
+        for x in x_min..=x_max {
+            for y in y_min..=y_max {
+                // worked out for the first sample of the pixel that gets shaded
+                let mut derivatives = None;
+                for sample in 0..self.samples.len() {
+                    let offset = self.samples[sample];
+                    let (sx, sy) = (x as f64 + offset.x, y as f64 + offset.y);
+                    if !inside_triangle(sx, sy, v) {
+                        continue;
+                    }
+                    // screen space z is affine in x and y, so depth is interpolated linearly
+                    let (alpha, beta, gamma) = compute_barycentric2d(sx, sy, v);
+                    let depth = alpha * v[0].z + beta * v[1].z + gamma * v[2].z;
+                    let ind = self.get_sample_index(x as usize, y as usize, sample);
+                    if !range.closer(depth, self.depth_sample[ind]) {
+                        continue;
+                    }
+                    let center = interpolate(alpha, beta, gamma, varying, &w);
+                    let (ddx, ddy) = if fs.needs_derivatives() {
+                        *derivatives.get_or_insert_with(|| {
+                            // the plane of the triangle carries on past its edges, so the neighbours need not be inside
+                            let (cx, cy) = (x as f64 + 0.5, y as f64 + 0.5);
+                            let c = at(cx, cy);
+                            (at(cx + 1.0, cy) + c * -1.0, at(cx, cy + 1.0) + c * -1.0)
+                        })
+                    } else {
+                        (center * 0.0, center * 0.0)
+                    };
+                    let frag = FragmentInput {
+                        position: Vector2::new(sx, sy),
+                        depth,
+                        w: 1.0 / (alpha / w[0] + beta / w[1] + gamma / w[2]),
+                        varying: center,
+                        ddx,
+                        ddy,
+                        front_facing,
+                    };
+                    if let Some(color) = fs.shade(&frag) {
+                        self.depth_sample[ind] = depth;
+                        self.frame_sample[ind] = color;
+                        if self.taa.is_some() {
+                            self.velocity_sample[ind] = self.sample_velocity(sx, sy, depth, frag.w);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // turns the samples of the whole frame into pixels, call once after all draws of a frame
+    pub fn resolve(&mut self) {
+        let range = self.depth_range();
+        let filter = self.resolve_filter;
+        let reach = (filter.radius() + 0.5).ceil() as i64 - 1;
+        let n = self.samples.len();
+
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                let temp = self.get_index1(x as usize, y as usize);
+                let first = self.get_sample_index(x as usize, y as usize, 0);
+
+                // the samples were depth tested one by one, the pixel keeps the nearest of them
+                let nearest = (first..first + n).fold(first, |a, b| {
+                    if range.closer(self.depth_sample[b], self.depth_sample[a]) { b } else { a }
+                });
+                self.depth_buf[temp] = self.depth_sample[nearest];
+                self.velocity_buf[temp] = self.velocity_sample[nearest];
+
+                let mut color = Vector3::zeros();
+                let mut weight_sum = 0.0;
+                for ny in (y - reach).max(0)..=(y + reach).min(self.height as i64 - 1) {
+                    for nx in (x - reach).max(0)..=(x + reach).min(self.width as i64 - 1) {
+                        for (sample, offset) in self.samples.iter().enumerate() {
+                            let dx = (nx - x) as f64 + offset.x - 0.5;
+                            let dy = (ny - y) as f64 + offset.y - 0.5;
+                            let weight = filter.weight(dx, dy);
+                            if weight > 0.0 {
+                                color += self.frame_sample[self.get_sample_index(nx as usize, ny as usize, sample)] * weight;
+                                weight_sum += weight;
+                            }
+                        }
+                    }
+                }
+                let color = if weight_sum > 0.0 {
+                    color / weight_sum
+                } else {
+                    self.frame_sample[first..first + n].iter().sum::<Vector3<f64>>() / n as f64
+                };
+                self.frame_buf_0[temp] = color;
+            }
+        }
+
+        if let Some(settings) = self.taa {
+            let history = if self.history_valid { Some(&self.history_buf[..]) } else { None };
+            let blended = taa::blend(&self.frame_buf_0, history, &self.velocity_buf,
+                                     self.width as usize, self.height as usize, &settings);
+            self.history_buf.copy_from_slice(&blended);
+            self.history_valid = true;
+            self.frame_buf_0 = blended;
+        }
+        if let Some(settings) = &self.fxaa {
+            self.frame_buf_0 = fxaa::fxaa(&self.frame_buf_0, self.width as usize, self.height as usize, settings);
+        }
+        if self.ssaa > 1 {
+            self.frame_buf = ssaa::downsample(&self.frame_buf_0, self.width() as usize, self.height() as usize,
+                                              self.ssaa as usize, self.ssaa_filter);
+        } else {
+            self.frame_buf.copy_from_slice(&self.frame_buf_0);
+        }
+
+        self.frame_index += 1;
+        self.prev_mvp = std::mem::take(&mut self.cur_mvp);
+    }
+
+    // rebuilds the clip space position of the sample and reprojects it into the last frame
+    fn sample_velocity(&self, x: f64, y: f64, depth: f64, w: f64) -> Vector2<f64> {
+        let j = self.jitter();
+        let (x, y) = (x - j.x, y - j.y);
+        let ndc = Vector4::new(2.0 * x / self.width as f64 - 1.0,
+                               2.0 * y / self.height as f64 - 1.0,
+                               self.depth_range().unmap(depth),
+                               1.0);
+        let prev = self.reprojection * (ndc * w);
+        if prev.w <= 0.0 {
+            // was behind the camera, there is no history for it
+            return Vector2::new(f64::NAN, f64::NAN);
+        }
+        let prev = self.viewport(&prev);
+        Vector2::new(prev.x - x, prev.y - y)
+    }
+
+    pub fn frame_buffer(&self) -> &Vec<Vector3<f64>> {
+        &self.frame_buf
+    }
+
+    // at the render resolution, which is larger than width() x height() with SSAA
+    pub fn depth_buffer(&self) -> &Vec<f64> {
+        &self.depth_buf
+    }
+
+    // per pixel motion vectors of the last resolve, only filled in with TAA on
+    pub fn velocity_buffer(&self) -> &Vec<Vector2<f64>> {
+        &self.velocity_buf
+    }
+
+    // the output size, with SSAA the pipeline runs at a multiple of it
+    pub fn width(&self) -> u64 {
+        self.width / self.ssaa
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height / self.ssaa
+    }
+
+    // width / height, what get_projection_matrix expects
+    pub fn aspect_ratio(&self) -> f64 {
+        self.width as f64 / self.height as f64
+    }
+}
+    fn to_vec4(v3: Vector3<f64>, w: Option<f64>) -> Vector4<f64> {
+        Vector4::new(v3.x, v3.y, v3.z, w.unwrap_or(1.0))
+    }
+
+fn inside_triangle(x: f64, y: f64, v: &[Vector4<f64>; 3]) -> bool {
+    // either winding is accepted here, facing is decided by the cull mode before rasterizing
+    let v0_v1 = (v[1].x - v[0].x) * (y - v[0].y) - (v[1].y - v[0].y) * (x - v[0].x);
+    let v1_v2 = (v[2].x - v[1].x) * (y - v[1].y) - (v[2].y - v[1].y) * (x - v[1].x);
+    let v2_v0 = (v[0].x - v[2].x) * (y - v[2].y) - (v[0].y - v[2].y) * (x - v[2].x);
+
+    (v0_v1 > 0.0 && v1_v2 > 0.0 && v2_v0 > 0.0) || (v0_v1 < 0.0 && v1_v2 < 0.0 && v2_v0 < 0.0)
+}
+
+// twice the signed screen space area, positive for counter clockwise
+fn signed_area(v: &[Vector4<f64>; 3]) -> f64 {
+    (v[1].x - v[0].x) * (v[2].y - v[0].y) - (v[1].y - v[0].y) * (v[2].x - v[0].x)
+}
+
+// steepest change of the screen space depth per pixel over a clipped fan
+fn depth_slope(triangles: &[Triangle]) -> f64 {
+    triangles.iter().map(|t| {
+        let (a, b, c) = (t.v[0], t.v[1], t.v[2]);
+        let area = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+        if area.abs() < 1e-12 {
+            return 0.0;
+        }
+        let dzdx = ((b.z - a.z) * (c.y - a.y) - (c.z - a.z) * (b.y - a.y)) / area;
+        let dzdy = ((b.x - a.x) * (c.z - a.z) - (c.x - a.x) * (b.z - a.z)) / area;
+        dzdx.abs().max(dzdy.abs())
+    }).fold(0.0, f64::max)
+}
+
+fn compute_barycentric2d(x: f64, y: f64, v: &[Vector4<f64>; 3]) -> (f64, f64, f64) {
+    let c1 = (x * (v[1].y - v[2].y) + (v[2].x - v[1].x) * y + v[1].x * v[2].y - v[2].x * v[1].y)
+        / (v[0].x * (v[1].y - v[2].y) + (v[2].x - v[1].x) * v[0].y + v[1].x * v[2].y - v[2].x * v[1].y);
+    let c2 = (x * (v[2].y - v[0].y) + (v[0].x - v[2].x) * y + v[2].x * v[0].y - v[0].x * v[2].y)
+        / (v[1].x * (v[2].y - v[0].y) + (v[0].x - v[2].x) * v[1].y + v[2].x * v[0].y - v[0].x * v[2].y);
+    let c3 = (x * (v[0].y - v[1].y) + (v[1].x - v[0].x) * y + v[0].x * v[1].y - v[1].x * v[0].y)
+        / (v[2].x * (v[0].y - v[1].y) + (v[1].x - v[0].x) * v[2].y + v[0].x * v[1].y - v[1].x * v[0].y);
+    (c1, c2, c3)
+}
+
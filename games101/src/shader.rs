@@ -0,0 +1,90 @@
+use std::ops::{Add, Mul};
+use nalgebra::{Vector2, Vector3, Vector4};
+use crate::clip::Clip;
+
+// anything the rasterizer can interpolate across a triangle, e.g. a struct of colors, normals
+// and texture coordinates with component wise Add and Mul<f64>
+pub trait Varying: Copy + Add<Output = Self> + Mul<f64, Output = Self> {}
+
+impl<T> Varying for T where T: Copy + Add<Output = T> + Mul<f64, Output = T> {}
+
+// what the vertex shader hands on for one vertex
+#[derive(Clone, Copy, Debug)]
+pub struct VertexOutput<V> {
+    // homogeneous clip space, as after the mvp multiply of the fixed pipeline
+    pub position: Vector4<f64>,
+    pub varying: V,
+}
+
+impl<V: Varying> Clip for VertexOutput<V> {
+    fn position(&self) -> Vector4<f64> {
+        self.position
+    }
+
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        VertexOutput {
+            position: self.position + (other.position - self.position) * t,
+            varying: self.varying * (1.0 - t) + other.varying * t,
+        }
+    }
+}
+
+// what the fragment shader gets for one covered sample
+#[derive(Clone, Copy, Debug)]
+pub struct FragmentInput<V> {
+    // screen space position of the sample, y up
+    pub position: Vector2<f64>,
+    pub depth: f64,
+    // clip space w, the view space distance along the view direction
+    pub w: f64,
+    // perspective correct interpolation of the vertex varyings
+    pub varying: V,
+    // how much the varying changes one pixel to the right and one pixel up, for texture filtering.
+    // taken once per pixel at its center and zero unless the shader asks for them, see
+    // FragmentShader::needs_derivatives
+    pub ddx: V,
+    pub ddy: V,
+    // the triangle has the front face winding, see Rasterizer::set_front_face
+    pub front_facing: bool,
+}
+
+// runs once for every vertex of the buffer passed to Rasterizer::draw_shaded
+pub trait VertexShader<In, V> {
+    fn shade(&self, vertex: &In) -> VertexOutput<V>;
+}
+
+impl<In, V, F> VertexShader<In, V> for F where F: Fn(&In) -> VertexOutput<V> {
+    fn shade(&self, vertex: &In) -> VertexOutput<V> {
+        self(vertex)
+    }
+}
+
+// runs once for every covered sample that passes the depth test, returns the color in 0..255
+// or None to discard the sample
+pub trait FragmentShader<V> {
+    fn shade(&self, fragment: &FragmentInput<V>) -> Option<Vector3<f64>>;
+
+    // ddx and ddy cost two more interpolations a pixel, so they are only worked out when this is true
+    fn needs_derivatives(&self) -> bool {
+        false
+    }
+}
+
+impl<V, F> FragmentShader<V> for F where F: Fn(&FragmentInput<V>) -> Option<Vector3<f64>> {
+    fn shade(&self, fragment: &FragmentInput<V>) -> Option<Vector3<f64>> {
+        self(fragment)
+    }
+}
+
+// a closure shader that reads ddx and ddy, e.g. for Texture::sample_grad
+pub struct WithDerivatives<F>(pub F);
+
+impl<V, F> FragmentShader<V> for WithDerivatives<F> where F: Fn(&FragmentInput<V>) -> Option<Vector3<f64>> {
+    fn shade(&self, fragment: &FragmentInput<V>) -> Option<Vector3<f64>> {
+        (self.0)(fragment)
+    }
+
+    fn needs_derivatives(&self) -> bool {
+        true
+    }
+}
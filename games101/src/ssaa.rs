@@ -0,0 +1,33 @@
+use nalgebra::Vector3;
+use crate::msaa::ResolveFilter;
+
+// shrinks an image rendered at factor times the size, both are stored row by row from the top;
+// the filter radius is in output pixels
+pub fn downsample(src: &[Vector3<f64>], width: usize, height: usize, factor: usize, filter: ResolveFilter) -> Vec<Vector3<f64>> {
+    assert!(factor >= 1, "SSAA factor has to be at least 1");
+    let (src_w, src_h) = (width * factor, height * factor);
+    assert_eq!(src.len(), src_w * src_h, "image does not match {}x{}", src_w, src_h);
+    let f = factor as f64;
+    let reach = (filter.radius() * f).ceil() as i64;
+
+    let mut out = Vec::with_capacity(width * height);
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            let (cx, cy) = (x as f64 + 0.5, y as f64 + 0.5);
+            let (sx, sy) = (x * factor as i64, y * factor as i64);
+            let mut color = Vector3::zeros();
+            let mut weight_sum = 0.0;
+            for py in (sy - reach).max(0)..(sy + factor as i64 + reach).min(src_h as i64) {
+                for px in (sx - reach).max(0)..(sx + factor as i64 + reach).min(src_w as i64) {
+                    let weight = filter.weight((px as f64 + 0.5) / f - cx, (py as f64 + 0.5) / f - cy);
+                    if weight > 0.0 {
+                        color += src[py as usize * src_w + px as usize] * weight;
+                        weight_sum += weight;
+                    }
+                }
+            }
+            out.push(if weight_sum > 0.0 { color / weight_sum } else { src[sy as usize * src_w + sx as usize] });
+        }
+    }
+    out
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use nalgebra::Vector3;
+use crate::mesh::{face_normal, Mesh, MeshError};
+use crate::utils::V3d;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StlFormat {
+    Ascii,
+    Binary,
+}
+
+fn malformed(message: String) -> MeshError {
+    MeshError::Malformed(message)
+}
+
+// stl repeats every corner, equal positions are merged back into one vertex
+fn weld(corners: &[V3d]) -> Mesh {
+    let mut positions = Vec::new();
+    let mut seen = HashMap::new();
+    let mut index = |p: V3d| *seen.entry(p.map(f64::to_bits)).or_insert_with(|| {
+        positions.push(p);
+        positions.len() - 1
+    });
+    let indices: Vec<_> = corners.chunks(3).map(|t| Vector3::new(index(t[0]), index(t[1]), index(t[2]))).collect();
+    let colors = vec![V3d::new(255.0, 255.0, 255.0); positions.len()];
+    Mesh::new(positions, colors, indices)
+}
+
+fn read_binary(data: &[u8], count: usize) -> Vec<V3d> {
+    let f32_at = |i: usize| f32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]) as f64;
+    // 50 bytes per triangle, the normal comes first and the attribute bytes last
+    (0..count).flat_map(|t| (1..4).map(move |v| 84 + 50 * t + 12 * v))
+        .map(|at| V3d::new(f32_at(at), f32_at(at + 4), f32_at(at + 8)))
+        .collect()
+}
+
+fn read_ascii(text: &str) -> Result<Vec<V3d>, MeshError> {
+    let mut corners = Vec::new();
+    let mut in_loop = 0;
+    for (i, line) in text.lines().enumerate() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let err = |message: &str| malformed(format!("line {}: {}", i + 1, message));
+        match words[..] {
+            [] | ["solid", ..] | ["endsolid", ..] | ["facet", ..] | ["endfacet"] => {}
+            ["outer", "loop"] => in_loop = 0,
+            ["vertex", x, y, z] => {
+                let parse = |s: &str| s.parse::<f64>().map_err(|_| err(&format!("`{}` is not a number", s)));
+                corners.push(V3d::new(parse(x)?, parse(y)?, parse(z)?));
+                in_loop += 1;
+            }
+            ["endloop"] if in_loop == 3 => {}
+            ["endloop"] => return Err(err(&format!("a facet needs 3 vertices, got {}", in_loop))),
+            _ => return Err(err(&format!("unexpected `{}`", line.trim()))),
+        }
+    }
+    if !corners.len().is_multiple_of(3) {
+        return Err(malformed("the last facet is not closed".to_string()));
+    }
+    Ok(corners)
+}
+
+// binary files may start with `solid` too, so the size decides
+pub fn read_stl<R: Read>(mut reader: R) -> Result<Mesh, MeshError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    if data.len() >= 84 {
+        let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as usize;
+        if data.len() == 84 + 50 * count {
+            return Ok(weld(&read_binary(&data, count)));
+        }
+    }
+    let text = std::str::from_utf8(&data).ok().filter(|t| t.trim_start().starts_with("solid"))
+        .ok_or_else(|| malformed("neither an ascii stl nor a binary one of the right size".to_string()))?;
+    Ok(weld(&read_ascii(text)?))
+}
+
+pub fn load_stl<P: AsRef<Path>>(path: P) -> Result<Mesh, MeshError> {
+    read_stl(File::open(path)?)
+}
+
+// only the triangles, stl has no colors. binary files store single precision floats
+pub fn write_stl<W: Write>(mut w: W, mesh: &Mesh, format: StlFormat) -> io::Result<()> {
+    let facets = mesh.indices.iter().map(|t| {
+        let n = face_normal(&mesh.positions, t).try_normalize(1e-12).unwrap_or_else(V3d::zeros);
+        (n, [mesh.positions[t.x], mesh.positions[t.y], mesh.positions[t.z]])
+    });
+    match format {
+        StlFormat::Ascii => {
+            writeln!(w, "solid games101")?;
+            for (n, corners) in facets {
+                writeln!(w, "facet normal {} {} {}\nouter loop", n.x, n.y, n.z)?;
+                for p in corners {
+                    writeln!(w, "vertex {} {} {}", p.x, p.y, p.z)?;
+                }
+                writeln!(w, "endloop\nendfacet")?;
+            }
+            writeln!(w, "endsolid games101")?;
+        }
+        StlFormat::Binary => {
+            let mut header = [0u8; 80];
+            header[..8].copy_from_slice(b"games101");
+            w.write_all(&header)?;
+            w.write_all(&(mesh.indices.len() as u32).to_le_bytes())?;
+            for (n, corners) in facets {
+                for v in std::iter::once(n).chain(corners) {
+                    for x in v.iter() {
+                        w.write_all(&(*x as f32).to_le_bytes())?;
+                    }
+                }
+                w.write_all(&[0, 0])?;
+            }
+        }
+    }
+    w.flush()
+}
+
+pub fn save_stl<P: AsRef<Path>>(path: P, mesh: &Mesh, format: StlFormat) -> io::Result<()> {
+    write_stl(BufWriter::new(File::create(path)?), mesh, format)
+}
@@ -0,0 +1,84 @@
+use nalgebra::{Vector2, Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TaaSettings {
+    // weight of the new frame, the history keeps 1 - blend
+    pub blend: f64,
+    // clamp the history to the colors around the pixel in the new frame, hides most ghosting
+    pub clamp: bool,
+    // length of the Halton(2, 3) jitter sequence before it repeats
+    pub jitter_samples: u64,
+}
+
+impl Default for TaaSettings {
+    fn default() -> Self {
+        TaaSettings { blend: 0.1, clamp: true, jitter_samples: 8 }
+    }
+}
+
+// subpixel offset of a frame in pixels, in (-0.5, 0.5)
+pub fn jitter_offset(frame: u64, samples: u64) -> Vector2<f64> {
+    let i = frame % samples.max(1) + 1;
+    Vector2::new(halton(i, 2) - 0.5, halton(i, 3) - 0.5)
+}
+
+fn halton(mut index: u64, base: u64) -> f64 {
+    let (mut f, mut r) = (1.0, 0.0);
+    while index > 0 {
+        f /= base as f64;
+        r += f * (index % base) as f64;
+        index /= base;
+    }
+    r
+}
+
+// buffers are stored row by row from the top like Rasterizer::frame_buffer, the velocities
+// point to where the pixel was in the last frame, in pixels with y up as on screen
+pub fn blend(current: &[Vector3<f64>], history: Option<&[Vector3<f64>]>, velocity: &[Vector2<f64>],
+             width: usize, height: usize, settings: &TaaSettings) -> Vec<Vector3<f64>> {
+    let history = match history {
+        Some(history) => history,
+        None => return current.to_vec(),
+    };
+    let at = |buf: &[Vector3<f64>], x: i64, y: i64| {
+        buf[y.clamp(0, height as i64 - 1) as usize * width + x.clamp(0, width as i64 - 1) as usize]
+    };
+
+    let mut out = Vec::with_capacity(current.len());
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            let i = y as usize * width + x as usize;
+            let v = velocity[i];
+            let prev = Vector2::new(x as f64 + 0.5 + v.x, y as f64 + 0.5 - v.y);
+            if !prev.x.is_finite() || !prev.y.is_finite()
+                || prev.x < 0.0 || prev.y < 0.0 || prev.x > width as f64 || prev.y > height as f64 {
+                // nothing to reproject from, start over from this frame
+                out.push(current[i]);
+                continue;
+            }
+
+            // bilinear lookup in the history
+            let (fx, fy) = (prev.x - 0.5, prev.y - 0.5);
+            let (x0, y0) = (fx.floor(), fy.floor());
+            let (tx, ty) = (fx - x0, fy - y0);
+            let (x0, y0) = (x0 as i64, y0 as i64);
+            let mut old = at(history, x0, y0) * ((1.0 - tx) * (1.0 - ty))
+                + at(history, x0 + 1, y0) * (tx * (1.0 - ty))
+                + at(history, x0, y0 + 1) * ((1.0 - tx) * ty)
+                + at(history, x0 + 1, y0 + 1) * (tx * ty);
+
+            if settings.clamp {
+                let mut lo = current[i];
+                let mut hi = current[i];
+                for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+                    let c = at(current, x + dx, y + dy);
+                    lo = lo.inf(&c);
+                    hi = hi.sup(&c);
+                }
+                old = old.sup(&lo).inf(&hi);
+            }
+            out.push(old + (current[i] - old) * settings.blend);
+        }
+    }
+    out
+}
@@ -0,0 +1,169 @@
+use std::path::Path;
+use image::{ImageResult, RgbImage};
+use nalgebra::{Vector2, Vector3};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextureFilter {
+    Nearest,
+    #[default]
+    Bilinear,
+    // bilinear in the two mip levels around the footprint of the pixel, blended by the fraction
+    Trilinear,
+}
+
+// what happens to texture coordinates outside [0, 1]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    #[default]
+    Repeat,
+    // the edge texels are stretched out
+    Clamp,
+    // every other repeat is flipped
+    Mirror,
+    // the border color
+    Border,
+}
+
+// one mip level, texels are stored row by row from the top like an image
+#[derive(Clone, Debug)]
+struct Level {
+    width: usize,
+    height: usize,
+    texels: Vec<Vector3<f64>>,
+}
+
+// colors are 0..255, u runs left to right and v bottom to top as in assignment 3
+#[derive(Clone, Debug)]
+pub struct Texture {
+    levels: Vec<Level>,
+    pub filter: TextureFilter,
+    pub wrap: Wrap,
+    pub border: Vector3<f64>,
+}
+
+impl Texture {
+    pub fn new(width: usize, height: usize, texels: Vec<Vector3<f64>>) -> Self {
+        assert!(width > 0 && height > 0, "texture has to be at least 1x1");
+        assert_eq!(texels.len(), width * height, "texels do not match {}x{}", width, height);
+        Texture {
+            levels: vec![Level { width, height, texels }],
+            filter: TextureFilter::default(),
+            wrap: Wrap::default(),
+            border: Vector3::zeros(),
+        }
+    }
+
+    pub fn from_image(image: &RgbImage) -> Self {
+        let texels = image.pixels().map(|p| Vector3::new(p[0] as f64, p[1] as f64, p[2] as f64)).collect();
+        Texture::new(image.width() as usize, image.height() as usize, texels)
+    }
+
+    // any format the image crate was built with, png, bmp or ppm
+    pub fn load<P: AsRef<Path>>(path: P) -> ImageResult<Self> {
+        Ok(Texture::from_image(&image::open(path)?.to_rgb8()))
+    }
+
+    pub fn width(&self) -> usize {
+        self.levels[0].width
+    }
+
+    pub fn height(&self) -> usize {
+        self.levels[0].height
+    }
+
+    // 1 until generate_mipmaps() is called
+    pub fn levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    // halves the texture down to 1x1, each texel is the average of the 2x2 above it
+    pub fn generate_mipmaps(&mut self) {
+        self.levels.truncate(1);
+        while let Some(last) = self.levels.last().filter(|l| l.width > 1 || l.height > 1) {
+            let (width, height) = ((last.width / 2).max(1), (last.height / 2).max(1));
+            let mut texels = Vec::with_capacity(width * height);
+            for y in 0..height {
+                for x in 0..width {
+                    // odd sizes reuse the last row or column
+                    let (x0, x1) = (2 * x, (2 * x + 1).min(last.width - 1));
+                    let (y0, y1) = (2 * y, (2 * y + 1).min(last.height - 1));
+                    let at = |x: usize, y: usize| last.texels[y * last.width + x];
+                    texels.push((at(x0, y0) + at(x1, y0) + at(x0, y1) + at(x1, y1)) / 4.0);
+                }
+            }
+            self.levels.push(Level { width, height, texels });
+        }
+    }
+
+    // the texel at column x and row y of a level, y from the bottom, after the wrap mode
+    fn texel(&self, level: usize, x: i64, y: i64) -> Vector3<f64> {
+        let l = &self.levels[level];
+        match (self.wrap_index(x, l.width), self.wrap_index(y, l.height)) {
+            (Some(x), Some(y)) => l.texels[(l.height - 1 - y) * l.width + x],
+            _ => self.border,
+        }
+    }
+
+    fn wrap_index(&self, i: i64, size: usize) -> Option<usize> {
+        let n = size as i64;
+        match self.wrap {
+            Wrap::Repeat => Some(i.rem_euclid(n) as usize),
+            Wrap::Clamp => Some(i.clamp(0, n - 1) as usize),
+            Wrap::Mirror => {
+                let m = i.rem_euclid(2 * n);
+                Some(if m >= n { 2 * n - 1 - m } else { m } as usize)
+            }
+            Wrap::Border => (0..n).contains(&i).then_some(i as usize),
+        }
+    }
+
+    fn nearest(&self, level: usize, uv: Vector2<f64>) -> Vector3<f64> {
+        let l = &self.levels[level];
+        self.texel(level, (uv.x * l.width as f64).floor() as i64, (uv.y * l.height as f64).floor() as i64)
+    }
+
+    // texel centers sit at +0.5
+    fn bilinear(&self, level: usize, uv: Vector2<f64>) -> Vector3<f64> {
+        let l = &self.levels[level];
+        let (fx, fy) = (uv.x * l.width as f64 - 0.5, uv.y * l.height as f64 - 0.5);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        self.texel(level, x0, y0) * ((1.0 - tx) * (1.0 - ty))
+            + self.texel(level, x0 + 1, y0) * (tx * (1.0 - ty))
+            + self.texel(level, x0, y0 + 1) * ((1.0 - tx) * ty)
+            + self.texel(level, x0 + 1, y0 + 1) * (tx * ty)
+    }
+
+    // the full resolution level, trilinear has no footprint here and is bilinear
+    pub fn sample(&self, uv: Vector2<f64>) -> Vector3<f64> {
+        match self.filter {
+            TextureFilter::Nearest => self.nearest(0, uv),
+            _ => self.bilinear(0, uv),
+        }
+    }
+
+    // bilinear at a fractional mip level, clamped to the levels there are
+    pub fn sample_level(&self, uv: Vector2<f64>, lod: f64) -> Vector3<f64> {
+        let lod = lod.clamp(0.0, (self.levels.len() - 1) as f64);
+        let (lo, hi) = (lod.floor() as usize, lod.ceil() as usize);
+        let t = lod - lo as f64;
+        self.bilinear(lo, uv) * (1.0 - t) + self.bilinear(hi, uv) * t
+    }
+
+    // the mip level whose texels are about as big as the pixel, from how far the texture
+    // coordinates move to the next pixel in x and in y
+    pub fn lod(&self, ddx: Vector2<f64>, ddy: Vector2<f64>) -> f64 {
+        let size = Vector2::new(self.width() as f64, self.height() as f64);
+        let rho = ddx.component_mul(&size).norm().max(ddy.component_mul(&size).norm());
+        if rho > 0.0 { rho.log2().max(0.0) } else { 0.0 }
+    }
+
+    // uses the derivatives for trilinear filtering, the other filters ignore them
+    pub fn sample_grad(&self, uv: Vector2<f64>, ddx: Vector2<f64>, ddy: Vector2<f64>) -> Vector3<f64> {
+        match self.filter {
+            TextureFilter::Trilinear => self.sample_level(uv, self.lod(ddx, ddy)),
+            _ => self.sample(uv),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::ops::Mul;
+use nalgebra::{Matrix3, Matrix4, Rotation3, UnitQuaternion, Vector3, Vector4};
+use crate::utils::{get_normal_matrix, V3d};
+
+// an affine transform of homogeneous points, angles are in degrees as in the assignments.
+// the chaining methods apply their step after what is there already, so
+// Transform::scaling(s).rotate(axis, a).translate(t) scales first and translates last
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    matrix: Matrix4<f64>,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Transform { matrix: Matrix4::identity() }
+    }
+
+    pub fn from_matrix(matrix: Matrix4<f64>) -> Self {
+        Transform { matrix }
+    }
+
+    pub fn matrix(&self) -> Matrix4<f64> {
+        self.matrix
+    }
+
+    pub fn translation(offset: V3d) -> Self {
+        Transform::from_matrix(Matrix4::new_translation(&offset))
+    }
+
+    pub fn scaling(factors: V3d) -> Self {
+        Transform::from_matrix(Matrix4::new_nonuniform_scaling(&factors))
+    }
+
+    // each coordinate gains the others times a factor, e.g. xy is how much y is added to x
+    pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        Transform::from_matrix(Matrix4::new(
+            1.0, xy, xz, 0.0,
+            yx, 1.0, yz, 0.0,
+            zx, zy, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ))
+    }
+
+    // Rodrigues' formula, counter-clockwise when viewed from the axis tip toward the origin. the axis
+    // does not have to be normalized, a zero axis is no rotation
+    pub fn rotation(axis: V3d, angle: f64) -> Self {
+        let Some(n) = axis.try_normalize(0.0) else { return Transform::identity() };
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let cross = Matrix3::new(
+            0.0, -n.z, n.y,
+            n.z, 0.0, -n.x,
+            -n.y, n.x, 0.0,
+        );
+        let r = Matrix3::identity() * cos + n * n.transpose() * (1.0 - cos) + cross * sin;
+        Transform::from_matrix(r.to_homogeneous())
+    }
+
+    // the rotation about the axis through point instead of the origin
+    pub fn rotation_about(point: V3d, axis: V3d, angle: f64) -> Self {
+        Transform::translation(-point).rotate(axis, angle).translate(point)
+    }
+
+    // roll about x, then pitch about y, then yaw about z, as Rotation3::from_euler_angles
+    pub fn euler(roll: f64, pitch: f64, yaw: f64) -> Self {
+        Transform::rotation(Vector3::x(), roll)
+            .rotate(Vector3::y(), pitch)
+            .rotate(Vector3::z(), yaw)
+    }
+
+    pub fn from_quaternion(q: &UnitQuaternion<f64>) -> Self {
+        Transform::from_matrix(q.to_homogeneous())
+    }
+
+    // self first, then next
+    pub fn then(&self, next: &Transform) -> Self {
+        Transform::from_matrix(next.matrix * self.matrix)
+    }
+
+    pub fn translate(&self, offset: V3d) -> Self {
+        self.then(&Transform::translation(offset))
+    }
+
+    pub fn scale(&self, factors: V3d) -> Self {
+        self.then(&Transform::scaling(factors))
+    }
+
+    pub fn shear(&self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        self.then(&Transform::shearing(xy, xz, yx, yz, zx, zy))
+    }
+
+    pub fn rotate(&self, axis: V3d, angle: f64) -> Self {
+        self.then(&Transform::rotation(axis, angle))
+    }
+
+    pub fn rotate_about(&self, point: V3d, axis: V3d, angle: f64) -> Self {
+        self.then(&Transform::rotation_about(point, axis, angle))
+    }
+
+    // None when a scale or shear flattened space
+    pub fn inverse(&self) -> Option<Self> {
+        self.matrix.try_inverse().map(Transform::from_matrix)
+    }
+
+    // what the normals go through, see get_normal_matrix
+    pub fn normal_matrix(&self) -> Matrix3<f64> {
+        get_normal_matrix(&self.matrix)
+    }
+
+    pub fn transform_point(&self, p: V3d) -> V3d {
+        let h = self.matrix * Vector4::new(p.x, p.y, p.z, 1.0);
+        h.xyz() / h.w
+    }
+
+    // directions ignore the translation
+    pub fn transform_vector(&self, v: V3d) -> V3d {
+        (self.matrix * Vector4::new(v.x, v.y, v.z, 0.0)).xyz()
+    }
+
+    pub fn transform_normal(&self, n: V3d) -> V3d {
+        (self.normal_matrix() * n).normalize()
+    }
+}
+
+// like the matrices, a * b applies b first
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, other: Transform) -> Transform {
+        Transform::from_matrix(self.matrix * other.matrix)
+    }
+}
+
+impl From<Rotation3<f64>> for Transform {
+    fn from(r: Rotation3<f64>) -> Self {
+        Transform::from_matrix(r.to_homogeneous())
+    }
+}
+
+impl From<UnitQuaternion<f64>> for Transform {
+    fn from(q: UnitQuaternion<f64>) -> Self {
+        Transform::from_quaternion(&q)
+    }
+}
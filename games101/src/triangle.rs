@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use std::ops::{Add, Mul};
+use nalgebra::{Vector2, Vector3, Vector4};
+
+// v[i] holds the screen space x, y, z and the clip space w of the vertex
+pub struct Triangle {
+    pub v: [Vector4<f64>; 3],
+    pub color: [Vector3<f64>; 3],
+    pub tex_coords: [Vector2<f64>; 3],
+    pub normal: [Vector3<f64>; 3],
+}
+
+impl Default for Triangle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Triangle {
+    pub fn new() -> Self {
+        let v: Vector3<f64> = Vector3::new(0.0, 0.0, 0.0);
+        Triangle {
+            v: [Vector4::new(0.0, 0.0, 0.0, 1.0); 3],
+            color: [v; 3],
+            tex_coords: [Vector2::new(0.0, 0.0); 3],
+            normal: [v; 3],
+        }
+    }
+    pub fn set_vertex(&mut self, ind: usize, ver: Vector4<f64>) {
+        self.v[ind] = ver;
+    }
+    pub fn set_normal(&mut self, ind: usize, n: Vector3<f64>) {
+        self.normal[ind] = n;
+    }
+    // 0..255, anything outside is clamped
+    pub fn set_color(&mut self, ind: usize, r: f64, g: f64, b: f64) {
+        self.color[ind] = Vector3::new(r, g, b).map(|c| c.clamp(0.0, 255.0) / 255.0);
+    }
+    pub fn set_tex_coord(&mut self, ind: usize, s: f64, t: f64) {
+        self.tex_coords[ind] = Vector2::new(s, t);
+    }
+    pub fn to_vector4(&self) -> [Vector4<f64>; 3] {
+        self.v
+    }
+    pub fn get_color(&self) -> Vector3<f64> {
+        self.color[0] * 255.0 // color of the first vertex, the rasterizer blends all three
+    }
+}
+
+// weight every vertex by 1/w so the attribute is linear in view space rather than on screen
+pub fn interpolate<T>(alpha: f64, beta: f64, gamma: f64, vert: &[T; 3], w: &[f64; 3]) -> T
+    where T: Copy + Add<Output = T> + Mul<f64, Output = T> {
+    let (a, b, c) = (alpha / w[0], beta / w[1], gamma / w[2]);
+    let w_reciprocal = 1.0 / (a + b + c);
+    (vert[0] * a + vert[1] * b + vert[2] * c) * w_reciprocal
+}
\ No newline at end of file
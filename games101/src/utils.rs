@@ -0,0 +1,133 @@
+#[cfg(feature = "opencv")]
+use std::os::raw::c_void;
+use nalgebra::{Matrix3, Matrix4, Vector3, Vector4};
+use crate::transform::Transform;
+#[cfg(feature = "opencv")]
+use opencv::core::{Mat, MatTraitConst};
+#[cfg(feature = "opencv")]
+use opencv::imgproc::{COLOR_RGB2BGR, cvt_color};
+
+pub type V3d = Vector3<f64>;
+
+// a camera at eye_pos looking down -z, camera::Camera can turn
+pub fn get_view_matrix(eye_pos: V3d) -> Matrix4<f64> {
+    /*  implement your code here  */
+    Transform::translation(-eye_pos).matrix()
+}
+
+// a camera at eye looking at target, up picks the roll and does not have to be perpendicular.
+// the camera looks down -z with y up in view space, as get_view_matrix assumes
+pub fn get_look_at_matrix(eye: V3d, target: V3d, up: V3d) -> Matrix4<f64> {
+    let forward = (target - eye).normalize();
+    let right = forward.cross(&up).normalize();
+    let up = right.cross(&forward);
+    Matrix4::new(
+        right.x, right.y, right.z, -right.dot(&eye),
+        up.x, up.y, up.z, -up.dot(&eye),
+        -forward.x, -forward.y, -forward.z, forward.dot(&eye),
+        0.0, 0.0, 0.0, 1.0,
+    )
+}
+
+pub fn get_model_matrix(rotation_angle: f64) -> Matrix4<f64> {
+    /*  implement your code here  */
+    Transform::rotation(Vector3::z(), rotation_angle).matrix()
+}
+
+pub fn get_projection_matrix(eye_fov: f64, aspect_ratio: f64, z_near: f64, z_far: f64) -> Matrix4<f64> {
+    /*  implement your code here  */
+    let t = z_near * (eye_fov / 2.0).to_radians().tan();
+    let r = t * aspect_ratio;
+    get_frustum_matrix(-r, r, -t, t, z_near, z_far)
+}
+
+// a perspective projection whose window on the near plane spans [l, r] x [b, t], it does not
+// have to be centered
+pub fn get_frustum_matrix(l: f64, r: f64, b: f64, t: f64, z_near: f64, z_far: f64) -> Matrix4<f64> {
+    // the camera looks down -z, so the near and far planes sit at z = -z_near and z = -z_far
+    let n = -z_near;
+    let f = -z_far;
+
+    // squeezes the frustum into the box between the near and far planes
+    let persp = Matrix4::new(
+        n, 0.0, 0.0, 0.0,
+        0.0, n, 0.0, 0.0,
+        0.0, 0.0, n + f, -f * n,
+        0.0, 0.0, 1.0, 0.0,
+    );
+    // and the box into the unit cube
+    let ortho = box_to_cube(l, r, b, t, n, f);
+
+    // negate x, y and w so that w > 0 in front of the camera, which also maps near to -1 and far to 1
+    let flip = Matrix4::from_diagonal(&Vector4::new(-1.0, -1.0, 1.0, -1.0));
+
+    flip * ortho.matrix() * persp
+}
+
+// parallel projection of the box [l, r] x [b, t] between the near and far planes, near goes to
+// NDC z = -1 and far to 1 as with the perspective one, w stays 1
+pub fn get_orthographic_matrix(l: f64, r: f64, b: f64, t: f64, z_near: f64, z_far: f64) -> Matrix4<f64> {
+    // box_to_cube puts near at z = 1, flipping z brings it to -1 without the w trick of the frustum
+    box_to_cube(l, r, b, t, -z_near, -z_far).scale(Vector3::new(1.0, 1.0, -1.0)).matrix()
+}
+
+// centers the box [l, r] x [b, t] x [f, n] on the origin and scales it to [-1, 1], n and f are
+// z coordinates so near ends up at z = 1 and far at -1
+fn box_to_cube(l: f64, r: f64, b: f64, t: f64, n: f64, f: f64) -> Transform {
+    Transform::translation(Vector3::new(-(r + l) / 2.0, -(t + b) / 2.0, -(f + n) / 2.0))
+        .scale(Vector3::new(2.0 / (r - l), 2.0 / (t - b), 2.0 / (n - f)))
+}
+
+// counter clockwise about the axis, which is normalized first
+pub fn get_rotation(axis: Vector3<f64>, angle: f64) -> Matrix4<f64> {
+    Transform::rotation(axis, angle).matrix()
+}
+
+// cofactor matrix of the upper 3x3 of the model view matrix, keeps normals perpendicular to their
+// surface under non uniform scaling and shearing. it is the inverse transpose times the determinant,
+// so unlike the inverse it is still right when a zero scale flattens the model, and the lighting
+// normalizes anyway. a mirroring transform negates it so normals keep pointing out
+pub fn get_normal_matrix(model_view: &Matrix4<f64>) -> Matrix3<f64> {
+    let linear: Matrix3<f64> = model_view.fixed_view::<3, 3>(0, 0).into();
+    let (a, b, c) = (linear.column(0), linear.column(1), linear.column(2));
+    let cofactor = Matrix3::from_columns(&[b.cross(&c), c.cross(&a), a.cross(&b)]);
+    if linear.determinant() < 0.0 { -cofactor } else { cofactor }
+}
+#[cfg(feature = "opencv")]
+pub fn frame_buffer2cv_mat(frame_buffer: &[V3d], width: u64, height: u64) -> opencv::core::Mat {
+    // the Mat borrows the buffer, so its size has to match exactly
+    assert_eq!(frame_buffer.len() as u64, width * height, "frame buffer does not match {}x{}", width, height);
+    let mut image = unsafe {
+        Mat::new_rows_cols_with_data(
+            height as i32, width as i32,
+            opencv::core::CV_64FC3,
+            frame_buffer.as_ptr() as *mut c_void,
+            opencv::core::Mat_AUTO_STEP,
+        ).unwrap()
+    };
+    let mut img = Mat::copy(&image).unwrap();
+    image.convert_to(&mut img, opencv::core::CV_8UC3, 1.0, 1.0).expect("panic message");
+    cvt_color(&img, &mut image, COLOR_RGB2BGR, 0).unwrap();
+    image
+}
+
+// the optional `width height` arguments of the lab binaries, both have to be positive integers
+pub fn parse_size(width: &str, height: &str) -> Result<(u64, u64), String> {
+    let parse = |arg: &str| arg.parse::<u64>().ok().filter(|&n| n > 0)
+        .ok_or_else(|| format!("invalid size `{}`, expected a positive integer", arg));
+    Ok((parse(width)?, parse(height)?))
+}
+
+pub fn min(x1: f64, x2: f64) -> f64 {
+    if x1 < x2 {
+        return x1
+    }
+    x2
+}
+
+pub fn max(x1: f64, x2: f64) -> f64 {
+    if x1 < x2 {
+        return x2
+    }
+    x1
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use games101::camera::Camera;
+use games101::rasterizer::{Buffer, Primitive, Rasterizer};
+use games101::utils::*;
+use nalgebra::{Vector3, Vector4};
+
+fn close(a: Vector3<f64>, b: Vector3<f64>) -> bool {
+    (a - b).norm() < 1e-9
+}
+
+fn ndc(camera: &Camera, p: Vector3<f64>) -> Vector3<f64> {
+    let clip = camera.projection_matrix() * camera.view_matrix() * Vector4::new(p.x, p.y, p.z, 1.0);
+    clip.xyz() / clip.w
+}
+
+#[test]
+fn look_at_and_field_of_view() {
+    // looking down -z is what get_view_matrix does
+    let mut camera = Camera::new(Vector3::new(1.0, 2.0, 5.0), Vector3::new(1.0, 2.0, 0.0));
+    assert!((camera.view_matrix() - get_view_matrix(camera.position)).norm() < 1e-12);
+
+    // the target ends up in the middle of the screen whatever up is
+    camera.position = Vector3::new(3.0, -4.0, 2.0);
+    camera.up = Vector3::new(0.3, 1.0, 0.2);
+    let ndc_target = ndc(&camera, camera.target);
+    assert!(ndc_target.x.abs() < 1e-12 && ndc_target.y.abs() < 1e-12);
+
+    // the top of a 90 degree field of view is at 45 degrees
+    let mut camera = Camera { fov: 90.0, ..Default::default() };
+    assert!((ndc(&camera, Vector3::new(0.0, 2.0, 3.0)).y - 1.0).abs() < 1e-12);
+    camera.aspect_ratio = 2.0;
+    assert!((ndc(&camera, Vector3::new(4.0, 0.0, 3.0)).x - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn orbit_pan_and_dolly() {
+    let mut camera = Camera::default();
+    camera.orbit(90.0, 0.0);
+    assert!(close(camera.position, Vector3::new(5.0, 0.0, 0.0)));
+    camera.orbit(0.0, 45.0);
+    assert!(close(camera.position, Vector3::new(5.0, 5.0, 0.0) / 2f64.sqrt()));
+    // it stops short of going over the top
+    camera.orbit(0.0, 120.0);
+    assert!((camera.position.norm() - 5.0).abs() < 1e-9 && camera.position.y < 5.0);
+    assert!(camera.view_matrix().iter().all(|x| x.is_finite()));
+
+    let mut camera = Camera::default();
+    camera.pan(1.0, 2.0);
+    assert!(close(camera.position, Vector3::new(1.0, 2.0, 5.0)) && close(camera.target, Vector3::new(1.0, 2.0, 0.0)));
+    camera.dolly(3.0);
+    assert!(close(camera.position, Vector3::new(1.0, 2.0, 2.0)));
+    camera.dolly(10.0);
+    assert!(close(camera.position, Vector3::new(1.0, 2.0, camera.z_near)));
+}
+
+#[test]
+fn first_person_look_and_walk() {
+    let mut camera = Camera::default();
+    camera.look(90.0, 0.0);
+    assert!(close(camera.forward(), Vector3::new(-1.0, 0.0, 0.0)));
+    camera.look(0.0, 30.0);
+    assert!((camera.forward().y - 0.5).abs() < 1e-9);
+    // walking forward while looking up stays on the ground
+    camera.walk(2.0, 1.0, 0.5);
+    assert!(close(camera.position, Vector3::new(-2.0, 0.5, 4.0)));
+    assert!((camera.forward().y - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn the_rasterizer_draws_what_the_camera_sees() {
+    const SIZE: u64 = 40;
+    // a triangle facing +x, edge on to the default camera
+    let mut r = Rasterizer::new(SIZE, SIZE);
+    let pos_id = r.load_position(&[Vector3::new(0.0, -1.0, 1.0), Vector3::new(0.0, -1.0, -1.0), Vector3::new(0.0, 1.5, 0.0)]);
+    let ind_id = r.load_indices(&[Vector3::new(0, 1, 2)]);
+    let col_id = r.load_colors(&[Vector3::new(255.0, 0.0, 0.0); 3]);
+    let covered = |r: &mut Rasterizer, camera: &Camera| {
+        r.clear(Buffer::Both);
+        r.set_model(get_model_matrix(0.0));
+        r.set_camera(camera);
+        r.draw(pos_id, ind_id, col_id, Primitive::Triangle);
+        r.resolve();
+        r.frame_buffer().iter().filter(|c| c.x > 0.0).count()
+    };
+    let mut camera = Camera::default();
+    assert_eq!(covered(&mut r, &camera), 0);
+    camera.orbit(90.0, 0.0);
+    let side = covered(&mut r, &camera);
+    assert!(side > 100, "{}", side);
+    assert_eq!(r.frame_buffer()[(SIZE / 2 * SIZE + SIZE / 2) as usize], Vector3::new(255.0, 0.0, 0.0));
+    camera.dolly(2.0);
+    assert!(covered(&mut r, &camera) > 2 * side);
+}
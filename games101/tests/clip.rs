@@ -0,0 +1,114 @@
+use games101::clip::{clip_line, clip_polygon, frustum_planes, ClipVertex};
+use games101::rasterizer::Primitive;
+use games101::utils::*;
+use nalgebra::{Vector3, Vector4};
+
+mod common;
+use common::fill;
+
+// the color is made from the position, so they can be checked wherever a cut lands
+fn vertex(x: f64, y: f64, z: f64, w: f64) -> ClipVertex {
+    ClipVertex {
+        pos: Vector4::new(x, y, z, w),
+        color: Vector3::new(x + y, z, w),
+    }
+}
+
+fn consistent(v: &ClipVertex) -> bool {
+    let p = v.pos;
+    let expected = vertex(p.x, p.y, p.z, p.w);
+    (v.color - expected.color).norm() < 1e-12
+}
+
+fn inside(v: &ClipVertex, planes: &[Vector4<f64>]) -> bool {
+    planes.iter().all(|p| p.dot(&v.pos) > -1e-12)
+}
+
+#[test]
+fn polygons_gain_and_lose_corners_at_the_planes() {
+    let planes = frustum_planes();
+    let (near, far) = (&planes[4..5], &planes[5..6]);
+    // one corner behind the near plane turns the triangle into a quad
+    let one_out = vec![vertex(0.0, 0.5, -3.0, 1.0), vertex(0.5, 0.0, 0.0, 1.0), vertex(-0.5, 0.0, 0.0, 1.0)];
+    let quad = clip_polygon(one_out.clone(), near);
+    assert_eq!(quad.len(), 4);
+    assert!(quad.iter().all(|v| inside(v, near) && consistent(v)));
+    // the new corners are a third of the way along the edges, where z = -w
+    assert!(quad.iter().filter(|v| (v.pos.z + v.pos.w).abs() < 1e-12).all(|v| (v.pos.y - 0.5 / 3.0).abs() < 1e-12));
+    // that corner is fine for the far plane
+    assert_eq!(clip_polygon(one_out, far).len(), 3);
+
+    // two corners beyond the far plane leave a smaller triangle
+    let two_out = vec![vertex(0.0, 0.0, 0.5, 1.0), vertex(0.5, 0.0, 3.0, 1.0), vertex(-0.5, 0.0, 3.0, 1.0)];
+    let small = clip_polygon(two_out.clone(), &planes);
+    assert_eq!(small.len(), 3);
+    assert!(small.iter().all(|v| inside(v, &planes) && consistent(v)));
+    assert!(clip_polygon(two_out, &[Vector4::new(0.0, 0.0, -1.0, -1.0)]).is_empty());
+
+    // a user plane x >= -0.1 cuts off one corner
+    let mut user = planes.to_vec();
+    user.push(Vector4::new(1.0, 0.0, 0.0, 0.1));
+    let cut = clip_polygon(vec![vertex(-0.5, -0.5, 0.0, 1.0), vertex(0.5, -0.5, 0.0, 1.0), vertex(0.0, 0.5, 0.0, 1.0)], &user);
+    assert_eq!(cut.len(), 4);
+    assert!(cut.iter().all(|v| v.pos.x >= -0.1 - 1e-12 && consistent(v)));
+}
+
+#[test]
+fn lines_are_cut_to_the_part_inside() {
+    let planes = frustum_planes();
+    // through the near and the far plane
+    let (a, b) = clip_line(&vertex(0.2, 0.0, -4.0, 2.0), &vertex(0.2, 0.0, 6.0, 2.0), &planes).unwrap();
+    assert!((a.pos.z + 2.0).abs() < 1e-12 && (b.pos.z - 2.0).abs() < 1e-12);
+    assert!(consistent(&a) && consistent(&b));
+    // inside already, nothing changes
+    let (c, d) = (vertex(0.1, 0.2, 0.3, 1.0), vertex(-0.1, -0.2, -0.3, 1.0));
+    let (a, b) = clip_line(&c, &d, &planes).unwrap();
+    assert_eq!((a.pos, b.pos), (c.pos, d.pos));
+    // beside the frustum, or outside a user plane
+    assert!(clip_line(&vertex(2.0, 0.0, 0.0, 1.0), &vertex(3.0, 1.0, 0.0, 1.0), &planes).is_none());
+    assert!(clip_line(&c, &d, &[Vector4::new(0.0, 1.0, 0.0, -0.5)]).is_none());
+}
+
+#[test]
+fn user_planes_and_the_eye_passing_the_triangles() {
+    const SIZE: u64 = 100;
+    let lab = [Vector3::new(2.0, 0.0, -2.0), Vector3::new(0.0, 2.0, -2.0), Vector3::new(-2.0, 0.0, -2.0)];
+    // a floor from in front of the camera to far behind it
+    let floor = [Vector3::new(-1.0, -1.0, -30.0), Vector3::new(1.0, -1.0, -30.0), Vector3::new(0.0, -1.0, 20.0)];
+    let white = Vector3::new(255.0, 255.0, 255.0);
+    let lit = |r: &games101::rasterizer::Rasterizer| -> Vec<(u64, u64)> {
+        (0..SIZE).flat_map(|y| (0..SIZE).map(move |x| (x, y))).filter(|&(x, y)| common::pixel(r, x, y).x > 0.0).collect()
+    };
+
+    // x >= 0 in world space keeps the right half of the screen
+    let mut r = common::setup(SIZE, SIZE);
+    r.add_clip_plane(Vector4::new(1.0, 0.0, 0.0, 0.0));
+    fill(&mut r, &lab, white, Primitive::Triangle);
+    r.resolve();
+    let right = lit(&r);
+    assert!(!right.is_empty() && right.iter().all(|&(x, _)| x >= SIZE / 2));
+
+    for eye in [5.0, 0.0, -1.7, -2.2, -4.0] {
+        let mut r = common::setup(SIZE, SIZE);
+        r.set_view(get_view_matrix(Vector3::new(0.0, 0.0, eye)));
+        fill(&mut r, &lab, white, Primitive::Triangle);
+        r.resolve();
+        let pixels = lit(&r);
+        if eye > -2.0 {
+            // in front the triangle stays above the middle row
+            assert!(!pixels.is_empty() && pixels.iter().all(|&(_, y)| y < SIZE / 2), "eye at {}", eye);
+        } else {
+            // behind the eye nothing may come back mirrored
+            assert!(pixels.is_empty(), "eye at {}: {} pixels", eye, pixels.len());
+        }
+
+        let mut r = common::setup(SIZE, SIZE);
+        r.set_view(get_view_matrix(Vector3::new(0.0, 0.0, eye)));
+        fill(&mut r, &floor, white, Primitive::Triangle);
+        r.resolve();
+        // the floor is below the eye, the part behind it must not wrap over the top of the screen
+        let pixels = lit(&r);
+        assert!(pixels.iter().all(|&(_, y)| y >= SIZE / 2), "eye at {}", eye);
+        assert!(common::pixel(&r, SIZE / 2, SIZE - 1).x > 0.0, "eye at {}", eye);
+    }
+}
@@ -0,0 +1,39 @@
+// the scene setup most of the tests share, not every test binary uses all of it
+#![allow(dead_code)]
+
+use games101::camera::Projection;
+use games101::rasterizer::{Buffer, ColBufId, IndBufId, PosBufId, Primitive, Rasterizer};
+use games101::utils::*;
+use nalgebra::Vector3;
+
+pub const SIZE: u64 = 100;
+
+// the camera of the assignments: at (0, 0, 5) looking down -z, 45 degrees, near 0.1 and far 50
+pub fn reset(r: &mut Rasterizer) {
+    r.clear(Buffer::Both);
+    r.set_model(get_model_matrix(0.0));
+    r.set_view(get_view_matrix(Vector3::new(0.0, 0.0, 5.0)));
+    r.set_projection_params(Projection::new(45.0, r.aspect_ratio(), 0.1, 50.0));
+}
+
+pub fn setup(width: u64, height: u64) -> Rasterizer {
+    let mut r = Rasterizer::new(width, height);
+    reset(&mut r);
+    r
+}
+
+// y counts rows from the top, as the frame buffer does
+pub fn pixel(r: &Rasterizer, x: u64, y: u64) -> Vector3<f64> {
+    r.frame_buffer()[(y * r.width() + x) as usize]
+}
+
+// the buffers of a mesh, for tests that draw it more than once
+pub fn load(r: &mut Rasterizer, pos: &[Vector3<f64>], ind: &[Vector3<usize>], col: &[Vector3<f64>]) -> (PosBufId, IndBufId, ColBufId) {
+    (r.load_position(pos), r.load_indices(ind), r.load_colors(col))
+}
+
+// one flat colored triangle
+pub fn fill(r: &mut Rasterizer, pos: &[Vector3<f64>], color: Vector3<f64>, typ: Primitive) {
+    let (pos_id, ind_id, col_id) = load(r, pos, &[Vector3::new(0, 1, 2)], &[color; 3]);
+    r.draw(pos_id, ind_id, col_id, typ);
+}
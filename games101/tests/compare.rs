@@ -0,0 +1,28 @@
+use games101::compare::{compose, find_edge_crop, Crop, Panel};
+use image::{Rgb, RgbImage};
+
+#[test]
+fn the_crop_lands_on_the_difference() {
+    let a = RgbImage::from_pixel(64, 48, Rgb([0, 0, 0]));
+    let mut b = a.clone();
+    for y in 30..34 {
+        for x in 40..44 {
+            b.put_pixel(x, y, Rgb([255, 255, 255]));
+        }
+    }
+    let crop = find_edge_crop(&a, &b, 8);
+    assert_eq!(crop.size, 8);
+    assert!(crop.x <= 40 && crop.x + 8 >= 44 && crop.y <= 30 && crop.y + 8 >= 34, "{:?}", crop);
+}
+
+#[test]
+fn every_panel_gets_a_cell() {
+    let panels: Vec<Panel> = (0..5).map(|i| Panel {
+        label: format!("MODE {}", i),
+        image: RgbImage::from_pixel(40, 30, Rgb([i * 40, 0, 0])),
+    }).collect();
+    let out = compose(&panels, Crop { x: 10, y: 10, size: 10 }, 4, 3);
+    // cells are max(40, 10 * 4) wide, two rows of three
+    assert_eq!(out.width(), 8 + 3 * (40 + 8));
+    assert_eq!(out.height(), 8 + 2 * (14 + 8 + 30 + 8 + 40 + 8));
+}
@@ -0,0 +1,58 @@
+use games101::rasterizer::{CullMode, FrontFace, Primitive, Rasterizer, Stats};
+use nalgebra::Vector3;
+
+mod common;
+
+const SIZE: u64 = 200;
+
+// one counter clockwise triangle, one clockwise and one behind the camera
+fn render(cull_mode: CullMode, front_face: FrontFace) -> Rasterizer {
+    let mut r = Rasterizer::new(SIZE, SIZE);
+    let pos = vec![Vector3::new(-2.0, 0.0, -2.0),
+                   Vector3::new(-0.5, 0.0, -2.0),
+                   Vector3::new(-1.0, 1.5, -2.0),
+                   Vector3::new(0.5, 0.0, -2.0),
+                   Vector3::new(1.0, 1.5, -2.0),
+                   Vector3::new(2.0, 0.0, -2.0),
+                   Vector3::new(-1.0, 0.0, 8.0),
+                   Vector3::new(1.0, 0.0, 8.0),
+                   Vector3::new(0.0, 1.0, 8.0)];
+    let ind = vec![Vector3::new(0, 1, 2), Vector3::new(3, 4, 5), Vector3::new(6, 7, 8)];
+    let col = vec![Vector3::new(255.0, 255.0, 255.0); 9];
+    let (pos_id, ind_id, col_id) = common::load(&mut r, &pos, &ind, &col);
+
+    r.set_cull_mode(cull_mode);
+    r.set_front_face(front_face);
+    common::reset(&mut r);
+    r.draw(pos_id, ind_id, col_id, Primitive::Triangle);
+    r.resolve();
+    r
+}
+
+// (left lit, right lit), sampled at the centroid of each triangle
+fn lit(r: &Rasterizer) -> (bool, bool) {
+    let y = SIZE / 2 - 13;
+    let at = |x: u64| common::pixel(r, x, y).x > 0.0;
+    (at(SIZE / 2 - 30), at(SIZE / 2 + 30))
+}
+
+#[test]
+fn cull_mode_picks_the_faces_that_are_drawn() {
+    let none = render(CullMode::None, FrontFace::Ccw);
+    assert_eq!(lit(&none), (true, true));
+    assert_eq!(none.stats(), Stats { triangles: 3, clipped: 1, culled: 0 });
+
+    let back = render(CullMode::Back, FrontFace::Ccw);
+    assert_eq!(lit(&back), (true, false));
+    assert_eq!(back.stats(), Stats { triangles: 3, clipped: 1, culled: 1 });
+
+    let front = render(CullMode::Front, FrontFace::Ccw);
+    assert_eq!(lit(&front), (false, true));
+    assert_eq!(front.stats().culled, 1);
+}
+
+#[test]
+fn front_face_flips_the_winding() {
+    assert_eq!(lit(&render(CullMode::Back, FrontFace::Cw)), (false, true));
+    assert_eq!(lit(&render(CullMode::Front, FrontFace::Cw)), (true, false));
+}
@@ -0,0 +1,89 @@
+use games101::camera::DepthRange;
+use games101::rasterizer::{Primitive, Rasterizer};
+use nalgebra::Vector3;
+
+mod common;
+use common::{fill, pixel};
+
+const SIZE: u64 = 350;
+
+// a flat red triangle at z = -2 and a blue one tilted through it, so the blue one
+// is in front for x < 0 and behind for x > 0
+fn render(blue_first: bool) -> Rasterizer {
+    render_with(blue_first, None)
+}
+
+fn render_with(blue_first: bool, range: Option<DepthRange>) -> Rasterizer {
+    let mut r = Rasterizer::new(SIZE, SIZE);
+    if let Some(range) = range {
+        r.set_depth_range(range);
+    }
+    let red = vec![Vector3::new(-2.0, -1.5, -2.0),
+                   Vector3::new(2.0, -1.5, -2.0),
+                   Vector3::new(0.0, 1.5, -2.0)];
+    let blue = vec![Vector3::new(-2.0, -1.5, -1.0),
+                    Vector3::new(2.0, -1.5, -3.0),
+                    Vector3::new(0.0, 1.5, -2.0)];
+    let (red_col, blue_col) = (Vector3::new(255.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 255.0));
+
+    common::reset(&mut r);
+    if blue_first {
+        fill(&mut r, &blue, blue_col, Primitive::Triangle);
+        fill(&mut r, &red, red_col, Primitive::Triangle);
+    } else {
+        fill(&mut r, &red, red_col, Primitive::Triangle);
+        fill(&mut r, &blue, blue_col, Primitive::Triangle);
+    }
+    r.resolve();
+    r
+}
+
+#[test]
+fn interpenetrating_triangles_split_at_the_intersection_line() {
+    for blue_first in [false, true] {
+        let r = render(blue_first);
+        let (cx, cy) = (SIZE / 2, SIZE / 2);
+        for dx in [10, 20, 30] {
+            let left = pixel(&r, cx - dx, cy);
+            let right = pixel(&r, cx + dx, cy);
+            assert!(left.z > 250.0 && left.x < 5.0, "left of the line should be blue, got {:?}", left);
+            assert!(right.x > 250.0 && right.z < 5.0, "right of the line should be red, got {:?}", right);
+        }
+    }
+}
+
+#[test]
+fn resolved_depth_follows_the_surface() {
+    let r = render(false);
+    let depth = |x: u64| r.depth_buffer()[(SIZE / 2 * SIZE + x) as usize];
+    let (cx, near) = (SIZE / 2, 1e-9);
+    // left of the line the tilted blue triangle recedes, right of it the flat red one is visible
+    let left: Vec<f64> = [30, 20, 10].iter().map(|dx| depth(cx - dx)).collect();
+    let right: Vec<f64> = [10, 20, 30].iter().map(|dx| depth(cx + dx)).collect();
+    assert!(left.windows(2).all(|d| d[0] < d[1]), "blue depth should increase: {:?}", left);
+    assert!(right.windows(2).all(|d| (d[0] - d[1]).abs() < near), "red depth should be flat: {:?}", right);
+    assert!(left[2] < right[0]);
+}
+
+#[test]
+fn depth_range_only_changes_the_stored_depth() {
+    let reference = render(true);
+    for range in [DepthRange::unit(), DepthRange::reverse_z(), DepthRange::new(-1.0, 1.0)] {
+        let r = render_with(true, Some(range));
+        assert_eq!(r.frame_buffer(), reference.frame_buffer(), "{:?}", range);
+        let center = r.depth_buffer()[(SIZE / 2 * SIZE + SIZE / 2) as usize];
+        let (lo, hi) = (range.near.min(range.far), range.near.max(range.far));
+        assert!(center > lo && center < hi, "{} outside {:?}", center, range);
+    }
+}
+
+#[test]
+fn switching_the_depth_range_needs_no_clear() {
+    let mut r = common::setup(SIZE, SIZE);
+    r.set_depth_range(DepthRange::reverse_z());
+    assert!(r.depth_buffer().iter().all(|d| *d == f64::NEG_INFINITY));
+    fill(&mut r, &[Vector3::new(-2.0, -1.5, -2.0), Vector3::new(2.0, -1.5, -2.0), Vector3::new(0.0, 1.5, -2.0)],
+                 Vector3::new(255.0, 0.0, 0.0), Primitive::Triangle);
+    r.resolve();
+    assert_eq!(pixel(&r, SIZE / 2, SIZE / 2), Vector3::new(255.0, 0.0, 0.0));
+}
@@ -0,0 +1,57 @@
+use games101::export::{save_bmp, save_image, save_png, save_ppm};
+use games101::rasterizer::{Primitive, Rasterizer};
+use image::Rgb;
+use nalgebra::Vector3;
+
+mod common;
+use common::fill;
+
+const WIDTH: u64 = 64;
+const HEIGHT: u64 = 48;
+
+// a red triangle that only covers the upper half of the screen
+fn render() -> Rasterizer {
+    let mut r = common::setup(WIDTH, HEIGHT);
+    fill(&mut r, &[Vector3::new(-1.0, 0.2, -2.0), Vector3::new(1.0, 0.2, -2.0), Vector3::new(0.0, 1.2, -2.0)],
+         Vector3::new(255.0, 0.0, 0.0), Primitive::Triangle);
+    r.resolve();
+    r
+}
+
+#[test]
+fn every_format_reads_back_as_the_frame() {
+    let r = render();
+    let fb = r.frame_buffer();
+    let dir = std::env::temp_dir().join("games101_export_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let saved = [
+        (dir.join("frame.png"), save_png(dir.join("frame.png"), fb, WIDTH, HEIGHT)),
+        (dir.join("frame.ppm"), save_ppm(dir.join("frame.ppm"), fb, WIDTH, HEIGHT)),
+        (dir.join("frame.bmp"), save_bmp(dir.join("frame.bmp"), fb, WIDTH, HEIGHT)),
+        (dir.join("by_extension.png"), save_image(dir.join("by_extension.png"), fb, WIDTH, HEIGHT)),
+    ];
+    let images: Vec<_> = saved.into_iter().map(|(path, result)| {
+        result.unwrap();
+        (image::open(&path).unwrap().to_rgb8(), path)
+    }).collect();
+    std::fs::remove_dir_all(&dir).ok();
+
+    for (img, path) in images {
+        assert_eq!(img.dimensions(), (WIDTH as u32, HEIGHT as u32), "{:?}", path);
+        for (x, y, p) in img.enumerate_pixels() {
+            let c = fb[(y as u64 * WIDTH + x as u64) as usize].map(|c| c.round() as u8);
+            assert_eq!(*p, Rgb([c.x, c.y, c.z]), "{:?} at ({}, {})", path, x, y);
+        }
+        // the triangle is above the middle of the screen, so it is in the top half of the file
+        let red_rows: Vec<u32> = img.enumerate_pixels().filter(|(_, _, p)| p[0] > 0).map(|(_, y, _)| y).collect();
+        assert!(!red_rows.is_empty() && red_rows.iter().all(|&y| y < HEIGHT as u32 / 2), "{:?}", path);
+    }
+}
+
+#[test]
+fn unknown_extensions_and_wrong_sizes_are_rejected() {
+    let r = render();
+    assert!(save_image(std::env::temp_dir().join("games101_export_test.xyz"), r.frame_buffer(), WIDTH, HEIGHT).is_err());
+    let too_short = std::panic::catch_unwind(|| save_png(std::env::temp_dir().join("games101_export_short.png"), &r.frame_buffer()[1..], WIDTH, HEIGHT));
+    assert!(too_short.is_err());
+}
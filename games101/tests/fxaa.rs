@@ -0,0 +1,40 @@
+use games101::fxaa::{fxaa, FxaaQuality, FxaaSettings};
+use nalgebra::Vector3;
+
+const W: usize = 64;
+const H: usize = 32;
+
+// white below a staircase that rises one pixel every 16 columns, black above
+fn staircase() -> Vec<Vector3<f64>> {
+    (0..H).flat_map(|y| (0..W).map(move |x| {
+        if y >= 12 + x / 16 { Vector3::new(255.0, 255.0, 255.0) } else { Vector3::zeros() }
+    })).collect()
+}
+
+#[test]
+fn flat_areas_are_left_alone() {
+    let image = vec![Vector3::new(40.0, 80.0, 120.0); W * H];
+    assert_eq!(fxaa(&image, W, H, &FxaaSettings::default()), image);
+}
+
+#[test]
+fn long_steps_become_a_ramp() {
+    let image = staircase();
+    for quality in [FxaaQuality::Low, FxaaQuality::Medium, FxaaQuality::High, FxaaQuality::Extreme] {
+        let out = fxaa(&image, W, H, &FxaaSettings::preset(quality));
+        // the top white row of a step fades out towards the next step down
+        let row: Vec<f64> = (16..32).map(|x| out[13 * W + x].x).collect();
+        assert!(row[0] > row[15] + 50.0, "{:?}: {:?}", quality, row);
+        assert!(row.windows(2).all(|p| p[0] + 1e-9 >= p[1]), "{:?}: {:?}", quality, row);
+        assert!(out.iter().all(|c| (0.0..=255.0).contains(&c.x)));
+    }
+}
+
+#[test]
+fn the_left_and_right_neighbours_are_both_read() {
+    // a single bright column is a vertical edge on both sides, so it is softened symmetrically
+    let image: Vec<_> = (0..W * H).map(|i| if i % W == 20 { Vector3::new(255.0, 255.0, 255.0) } else { Vector3::zeros() }).collect();
+    let out = fxaa(&image, W, H, &FxaaSettings::default());
+    let (l, r) = (out[10 * W + 19].x, out[10 * W + 21].x);
+    assert!((l - r).abs() < 1e-9, "{} {}", l, r);
+}
@@ -0,0 +1,92 @@
+use games101::lighting::{Light, Lighting, Material, Surface};
+use games101::rasterizer::Rasterizer;
+use games101::utils::*;
+use nalgebra::{Matrix4, Vector3, Vector4};
+
+mod common;
+use common::SIZE;
+
+fn setup(lights: Vec<Light>) -> Rasterizer {
+    let mut r = common::setup(SIZE, SIZE);
+    r.set_lighting(Lighting { lights, ..Default::default() });
+    r
+}
+
+// a white square facing the camera, centered on (x, 0, z)
+fn quad(r: &mut Rasterizer, x: f64, z: f64, half: f64) {
+    let pos = [Vector3::new(x - half, -half, z), Vector3::new(x + half, -half, z),
+               Vector3::new(x + half, half, z), Vector3::new(x - half, half, z)];
+    let pos_id = r.load_position(&pos);
+    let ind_id = r.load_indices(&[Vector3::new(0, 1, 2), Vector3::new(0, 2, 3)]);
+    let col_id = r.load_colors(&[Vector3::new(255.0, 255.0, 255.0); 4]);
+    let normal_id = r.load_normals(&[Vector3::new(0.0, 0.0, 1.0); 4]);
+    r.draw_lit(pos_id, ind_id, col_id, normal_id);
+}
+
+fn pixel(r: &Rasterizer, x: u64, y: u64) -> f64 {
+    common::pixel(r, x, y).x
+}
+
+#[test]
+fn normal_matrix_keeps_normals_perpendicular() {
+    let mut model = Matrix4::new_nonuniform_scaling(&Vector3::new(3.0, 0.5, 1.0));
+    model[(0, 1)] = 0.7;
+    let model_view = get_view_matrix(Vector3::new(1.0, 2.0, 5.0)) * model;
+    let (tangent, normal) = (Vector3::new(1.0, 1.0, 0.0), Vector3::new(1.0, -1.0, 2.0));
+    assert_eq!(tangent.dot(&normal), 0.0);
+
+    let t = (model_view * Vector4::new(tangent.x, tangent.y, tangent.z, 0.0)).xyz();
+    assert!(t.dot(&(get_normal_matrix(&model_view) * normal)).abs() < 1e-12);
+    // transforming the normal like a position would not
+    assert!(t.dot(&(model_view * Vector4::new(normal.x, normal.y, normal.z, 0.0)).xyz()).abs() > 0.1);
+
+    // squashed flat onto y = 0, a normal that is not in the plane turns into the normal of the plane
+    let flat = get_normal_matrix(&Matrix4::new_nonuniform_scaling(&Vector3::new(2.0, 0.0, 1.0))) * normal;
+    assert!((flat.normalize() - Vector3::new(0.0, -1.0, 0.0)).norm() < 1e-12);
+    // a mirror keeps the normal on the outside, pointing away from the mirrored surface
+    let mirror = get_normal_matrix(&Matrix4::new_nonuniform_scaling(&Vector3::new(-1.0, 1.0, 1.0)));
+    assert_eq!(mirror * Vector3::new(1.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0));
+}
+
+#[test]
+fn lights_add_up_over_the_ambient_term() {
+    let surface = Surface { position: Vector3::new(0.0, 0.0, -5.0), normal: Vector3::new(0.0, 0.0, 1.0), color: Vector3::new(100.0, 100.0, 100.0) };
+    let a = Light::Point { position: Vector3::new(2.0, 1.0, 0.0), intensity: Vector3::new(5.0, 5.0, 5.0) };
+    let b = Light::Directional { direction: Vector3::new(0.3, 0.0, -1.0), intensity: Vector3::new(0.2, 0.1, 0.0) };
+    let shade = |lights: Vec<Light>| Lighting { lights, ..Default::default() }.shade(&surface);
+    let ambient = shade(vec![]);
+    assert!((ambient - Material::default().ka * 10.0 * 255.0).norm() < 1e-9);
+    let both = shade(vec![a, b]);
+    assert!((both - (shade(vec![a]) + shade(vec![b]) - ambient)).norm() < 1e-9);
+    // a light behind the surface only leaves the ambient term
+    let behind = Light::Directional { direction: Vector3::new(0.0, 0.0, 1.0), intensity: Vector3::new(9.0, 9.0, 9.0) };
+    assert!((shade(vec![behind]) - ambient).norm() < 1e-9);
+}
+
+#[test]
+fn point_lights_fall_off_and_highlight() {
+    // a light right at the camera puts the highlight in the middle of the square
+    let mut r = setup(vec![Light::Point { position: Vector3::new(0.0, 0.0, 5.0), intensity: Vector3::new(5.0, 5.0, 5.0) }]);
+    quad(&mut r, 0.0, 0.0, 2.0);
+    r.resolve();
+    let (c, corner) = (pixel(&r, SIZE / 2, SIZE / 2), pixel(&r, SIZE / 2 + 20, SIZE / 2 + 20));
+    assert!(c > corner + 30.0, "{} {}", c, corner);
+
+    // the nearer of two squares gets more of a point light, a directional light does not care
+    let far_light = Light::Point { position: Vector3::new(0.0, 0.0, 40.0), intensity: Vector3::new(1200.0, 1200.0, 1200.0) };
+    let sun = Light::Directional { direction: Vector3::new(0.0, 0.0, -1.0), intensity: Vector3::new(0.5, 0.5, 0.5) };
+    let side_by_side = |light| {
+        let mut r = setup(vec![light]);
+        r.set_lighting(Lighting { material: Material { ks: Vector3::zeros(), ..Default::default() }, ..r.lighting().clone() });
+        quad(&mut r, -1.0, 0.0, 0.5);
+        quad(&mut r, 1.5, -3.0, 0.5);
+        r.resolve();
+        // the brightest pixel of each, the edges are only partly covered
+        let brightest = |xs: std::ops::Range<u64>| xs.map(|x| pixel(&r, x, SIZE / 2)).fold(0.0, f64::max);
+        (brightest(0..SIZE / 2), brightest(SIZE / 2..SIZE))
+    };
+    let (near, far) = side_by_side(far_light);
+    assert!(near > far + 10.0, "{} {}", near, far);
+    let (near, far) = side_by_side(sun);
+    assert!((near - far).abs() < 1e-9, "{} {}", near, far);
+}
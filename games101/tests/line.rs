@@ -0,0 +1,81 @@
+use games101::line::{LineCap, LineJoin, LineMode, LineStyle};
+use games101::msaa::SamplePattern;
+use games101::rasterizer::{Buffer, Rasterizer};
+use nalgebra::{Matrix4, Vector3};
+
+const SIZE: u64 = 100;
+
+// with identity matrices the screen is [-1, 1], points are given in pixels here
+fn render(style: LineStyle, points: &[(f64, f64)], closed: bool) -> Rasterizer {
+    let mut r = Rasterizer::new(SIZE, SIZE);
+    r.set_msaa(1, SamplePattern::OrderedGrid);
+    r.set_model(Matrix4::identity());
+    r.set_view(Matrix4::identity());
+    r.set_projection(Matrix4::identity());
+    r.set_line_style(style);
+    r.clear(Buffer::Both);
+    let half = SIZE as f64 / 2.0;
+    let points: Vec<Vector3<f64>> = points.iter().map(|(x, y)| Vector3::new(x / half - 1.0, y / half - 1.0, 0.0)).collect();
+    r.draw_line_strip(&points, closed);
+    r.resolve();
+    r
+}
+
+fn green(r: &Rasterizer, x: u64, y: u64) -> f64 {
+    r.frame_buffer()[((SIZE - 1 - y) * SIZE + x) as usize].y
+}
+
+#[test]
+fn antialiased_lines_have_partial_pixels() {
+    let points = [(10.0, 20.3), (90.0, 60.7)];
+    let count = |r: &Rasterizer, partial: bool| r.frame_buffer().iter()
+        .filter(|c| if partial { c.y > 1.0 && c.y < 254.0 } else { c.y > 0.0 }).count();
+
+    let hard = render(LineStyle::default(), &points, false);
+    assert!(count(&hard, true) == 0 && count(&hard, false) > 70);
+
+    let smooth = render(LineStyle { mode: LineMode::Antialiased, ..Default::default() }, &points, false);
+    assert!(count(&smooth, true) > 70);
+    // about as much ink either way
+    let ink = |r: &Rasterizer| r.frame_buffer().iter().map(|c| c.y).sum::<f64>();
+    let ratio = ink(&smooth) / ink(&hard);
+    assert!(ratio > 0.8 && ratio < 1.25, "{}", ratio);
+}
+
+#[test]
+fn caps_shape_the_line_ends() {
+    let style = |cap| LineStyle { width: 6.0, cap, ..Default::default() };
+    let points = [(20.0, 50.5), (80.0, 50.5)];
+
+    let butt = render(style(LineCap::Butt), &points, false);
+    assert!(green(&butt, 79, 50) > 0.0 && green(&butt, 79, 52) > 0.0);
+    assert_eq!(green(&butt, 81, 50), 0.0);
+
+    let square = render(style(LineCap::Square), &points, false);
+    assert!(green(&square, 82, 50) > 0.0 && green(&square, 82, 52) > 0.0);
+    assert_eq!(green(&square, 84, 50), 0.0);
+
+    let round = render(style(LineCap::Round), &points, false);
+    assert!(green(&round, 82, 50) > 0.0);
+    assert_eq!(green(&round, 82, 52), 0.0);
+}
+
+#[test]
+fn joins_fill_the_outer_corner() {
+    let style = |join| LineStyle { width: 8.0, join, ..Default::default() };
+    let points = [(20.5, 20.5), (60.5, 20.5), (60.5, 80.5)];
+
+    // the strip turns left, the outer corner is below and to the right of the middle point
+    let miter = render(style(LineJoin::Miter), &points, false);
+    assert!(green(&miter, 63, 17) > 0.0);
+    for join in [LineJoin::Bevel, LineJoin::Round] {
+        let r = render(style(join), &points, false);
+        assert_eq!(green(&r, 63, 17), 0.0, "{:?}", join);
+        assert!(green(&r, 61, 19) > 0.0, "{:?}", join);
+    }
+
+    // a miter longer than the limit is beveled
+    let sharp = [(20.5, 20.5), (60.5, 20.5), (20.5, 26.5)];
+    let limited = render(LineStyle { miter_limit: 2.0, ..style(LineJoin::Miter) }, &sharp, false);
+    assert_eq!(green(&limited, 70, 22), 0.0);
+}
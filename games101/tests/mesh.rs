@@ -0,0 +1,130 @@
+use games101::mesh::{Mesh, MeshError};
+use games101::ply::{read_ply, write_ply, PlyFormat};
+use games101::rasterizer::Rasterizer;
+use games101::stl::{read_stl, write_stl, StlFormat};
+use nalgebra::{Vector2, Vector3};
+
+// the two triangles of the first assignment
+fn scene() -> (Rasterizer, Mesh) {
+    let mut r = Rasterizer::new(10, 10);
+    let pos = [Vector3::new(2.0, 0.0, -2.0), Vector3::new(0.0, 2.0, -2.0), Vector3::new(-2.0, 0.0, -2.0),
+               Vector3::new(3.5, -1.0, -5.0), Vector3::new(2.5, 1.5, -5.0), Vector3::new(-1.0, 0.5, -5.0)];
+    let col = [Vector3::new(217.0, 238.0, 185.0); 3].into_iter().chain([Vector3::new(185.0, 217.0, 238.0); 3]).collect::<Vec<_>>();
+    let pos_id = r.load_position(&pos);
+    let ind_id = r.load_indices(&[Vector3::new(0, 1, 2), Vector3::new(3, 4, 5)]);
+    let col_id = r.load_colors(&col);
+    let mesh = r.mesh(pos_id, ind_id, col_id);
+    (r, mesh)
+}
+
+#[test]
+fn ply_round_trips_in_every_format() {
+    let (mut r, mut mesh) = scene();
+    mesh.normals = vec![Vector3::new(0.0, 0.0, 1.0); 6];
+    mesh.tex_coords = (0..6).map(|i| Vector2::new(i as f64 * 0.1, 1.0 / 3.0)).collect();
+    for format in [PlyFormat::Ascii, PlyFormat::BinaryLittleEndian, PlyFormat::BinaryBigEndian] {
+        let mut bytes = Vec::new();
+        write_ply(&mut bytes, &mesh, format).unwrap();
+        assert_eq!(read_ply(bytes.as_slice()).unwrap(), mesh, "{:?}", format);
+    }
+    // what comes back can go straight into the rasterizer again
+    let buffers = r.load_mesh(&mesh);
+    assert!(buffers.normals.is_some() && buffers.tex_coords.is_some());
+}
+
+#[test]
+fn ply_reads_other_types_and_skips_what_it_does_not_know() {
+    let src = "ply
+format ascii 1.0
+comment a quad with float colors and an extra element
+element vertex 4
+property float x
+property float y
+property float z
+property float red
+property float green
+property float blue
+property uchar alpha
+element face 1
+property list uchar uint vertex_index
+property int flags
+element edge 1
+property int vertex1
+property int vertex2
+end_header
+0 0 0 1 0 0 255
+1 0 0 1 0 0 255
+1 1 0 1 0 0 255
+0 1 0 0 0 1 255
+4 0 1 2 3 7
+0 2
+";
+    let mesh = read_ply(src.as_bytes()).unwrap();
+    assert_eq!(mesh.indices, [Vector3::new(0, 1, 2), Vector3::new(0, 2, 3)]);
+    assert_eq!(mesh.colors[3], Vector3::new(0.0, 0.0, 255.0));
+    assert!(mesh.normals.is_empty() && mesh.tex_coords.is_empty());
+
+    let bad_index = src.replace("4 0 1 2 3 7", "3 0 1 9 7");
+    assert!(matches!(read_ply(bad_index.as_bytes()), Err(MeshError::IndexOutOfRange { index: 9, count: 4 })));
+    let truncated = &src[..src.len() - 10];
+    assert!(matches!(read_ply(truncated.as_bytes()), Err(MeshError::Malformed(_))));
+    assert!(matches!(read_ply(src.replace("float blue", "half blue").as_bytes()), Err(MeshError::Malformed(_))));
+    assert!(matches!(read_ply("solid x\n".as_bytes()), Err(MeshError::Malformed(_))));
+}
+
+#[test]
+fn ply_colors_are_scaled_by_their_type() {
+    let src = |ty: &str, rows: &str| format!("ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+property {0} red
+property {0} green
+property {0} blue
+element face 1
+property list uchar int vertex_indices
+end_header
+{1}3 0 1 2
+", ty, rows);
+    let mesh = read_ply(src("ushort", "0 0 0 65535 0 65535\n1 0 0 0 65535 0\n0 1 0 13107 0 0\n").as_bytes()).unwrap();
+    assert_eq!(mesh.colors, [Vector3::new(255.0, 0.0, 255.0), Vector3::new(0.0, 255.0, 0.0), Vector3::new(51.0, 0.0, 0.0)]);
+    // out of range floats and negative ints are clamped
+    let mesh = read_ply(src("float", "0 0 0 1.5 -0.5 0.2\n1 0 0 0 0 0\n0 1 0 0 0 0\n").as_bytes()).unwrap();
+    assert!((mesh.colors[0] - Vector3::new(255.0, 0.0, 51.0)).norm() < 1e-4);
+    let mesh = read_ply(src("int", "0 0 0 -7 0 0\n1 0 0 0 0 0\n0 1 0 0 0 0\n").as_bytes()).unwrap();
+    assert_eq!(mesh.colors[0], Vector3::zeros());
+}
+
+#[test]
+fn stl_round_trips_the_triangles() {
+    let (_, mut mesh) = scene();
+    // stl has no colors, everything comes back white
+    mesh.colors = vec![Vector3::new(255.0, 255.0, 255.0); 6];
+    for format in [StlFormat::Ascii, StlFormat::Binary] {
+        let mut bytes = Vec::new();
+        write_stl(&mut bytes, &mesh, format).unwrap();
+        assert_eq!(read_stl(bytes.as_slice()).unwrap(), mesh, "{:?}", format);
+    }
+
+    // a binary file whose header happens to start with solid
+    let mut bytes = Vec::new();
+    write_stl(&mut bytes, &mesh, StlFormat::Binary).unwrap();
+    bytes[..8].copy_from_slice(b"solid ab");
+    assert_eq!(read_stl(bytes.as_slice()).unwrap(), mesh);
+}
+
+#[test]
+fn stl_merges_shared_corners_and_reports_broken_facets() {
+    let facet = |a: &str, b: &str, c: &str| format!("facet normal 0 0 1\nouter loop\nvertex {}\nvertex {}\nvertex {}\nendloop\nendfacet\n", a, b, c);
+    let src = format!("solid quad\n{}{}endsolid quad\n", facet("0 0 0", "1 0 0", "1 1 0"), facet("0 0 0", "1 1 0", "0 1 0"));
+    let mesh = read_stl(src.as_bytes()).unwrap();
+    assert_eq!(mesh.positions.len(), 4);
+    assert_eq!(mesh.indices, [Vector3::new(0, 1, 2), Vector3::new(0, 2, 3)]);
+
+    let err = read_stl(src.replace("vertex 0 1 0\n", "").as_bytes()).unwrap_err();
+    assert_eq!(err.to_string(), "line 13: a facet needs 3 vertices, got 2");
+    assert!(matches!(read_stl(src.replace("1 1 0", "1 one 0").as_bytes()), Err(MeshError::Malformed(_))));
+    assert!(matches!(read_stl(&[0u8; 90][..]), Err(MeshError::Malformed(_))));
+}
@@ -0,0 +1,73 @@
+use games101::msaa::{ResolveFilter, SamplePattern, SAMPLE_COUNTS};
+use games101::rasterizer::{Primitive, Rasterizer};
+use nalgebra::{Vector2, Vector3};
+
+mod common;
+
+#[test]
+fn every_pattern_has_the_requested_count_inside_the_pixel() {
+    for pattern in [SamplePattern::OrderedGrid, SamplePattern::RotatedGrid, SamplePattern::Standard] {
+        for count in SAMPLE_COUNTS {
+            let offsets = pattern.offsets(count);
+            assert_eq!(offsets.len(), count);
+            assert!(offsets.iter().all(|o| (0.0..1.0).contains(&o.x) && (0.0..1.0).contains(&o.y)), "{:?} {}", pattern, count);
+            // so they can be handed back as a custom pattern
+            assert_eq!(SamplePattern::Custom(offsets.clone()).offsets(count), offsets);
+        }
+    }
+}
+
+#[test]
+fn rotated_grid_never_shares_a_row_or_column() {
+    for count in SAMPLE_COUNTS {
+        let offsets = SamplePattern::RotatedGrid.offsets(count);
+        for (i, a) in offsets.iter().enumerate() {
+            for b in &offsets[i + 1..] {
+                assert!(a.x != b.x && a.y != b.y, "{} samples: {:?} and {:?}", count, a, b);
+            }
+        }
+    }
+    let rgss = [(0.375, 0.125), (0.875, 0.375), (0.125, 0.625), (0.625, 0.875)];
+    let offsets = SamplePattern::RotatedGrid.offsets(4);
+    assert_eq!(offsets, rgss.iter().map(|&(x, y)| Vector2::new(x, y)).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn unsupported_counts_are_rejected() {
+    SamplePattern::OrderedGrid.offsets(3);
+}
+
+fn render(r: &mut Rasterizer, draw: bool) {
+    common::reset(r);
+    if draw {
+        let pos = [Vector3::new(2.0, 0.0, -2.0), Vector3::new(0.0, 2.0, -2.0), Vector3::new(-2.0, 0.0, -2.0)];
+        common::fill(r, &pos, Vector3::new(255.0, 128.0, 0.0), Primitive::Triangle);
+    }
+    r.resolve();
+}
+
+#[test]
+fn clear_drops_the_samples_of_the_last_frame() {
+    let mut r = Rasterizer::new(100, 100);
+    render(&mut r, true);
+    assert!(r.frame_buffer().iter().any(|c| c.x > 0.0));
+    render(&mut r, false);
+    assert!(r.frame_buffer().iter().all(|c| *c == Vector3::zeros()));
+}
+
+#[test]
+fn filters_keep_the_interior_and_only_soften_edges() {
+    for filter in [ResolveFilter::Box, ResolveFilter::Tent, ResolveFilter::Gaussian] {
+        for pattern in [SamplePattern::OrderedGrid, SamplePattern::RotatedGrid, SamplePattern::Standard] {
+            let mut r = Rasterizer::new(100, 100);
+            r.set_msaa(8, pattern);
+            r.set_resolve_filter(filter);
+            render(&mut r, true);
+            // row 40 from the top is inside the triangle around the center column
+            let center = r.frame_buffer()[40 * 100 + 50];
+            assert!((center - Vector3::new(255.0, 128.0, 0.0)).norm() < 1e-9, "{:?}: {:?}", filter, center);
+            assert!(r.frame_buffer().iter().all(|c| c.x <= 255.0 + 1e-9 && c.x >= 0.0));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use games101::obj::{load_obj, parse_mtl, parse_obj, Material, ObjError};
+use games101::rasterizer::Primitive;
+use nalgebra::{Vector2, Vector3};
+
+mod common;
+
+const QUADS: &str = "
+# two quads sharing an edge
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 0 0
+v 2 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+
+g left
+f 1/1/1 2/2/1 3/3/1 4/4/1
+g right
+usemtl red
+f -5/1 -2/2 -1/3 -4/4
+";
+
+fn red() -> HashMap<String, Material> {
+    let mtl = parse_mtl("newmtl red\nKd 1 0 0\nKs 0.5\nNs 32\nmap_Kd -s 1 1 1 bricks.png\n").unwrap();
+    mtl.into_iter().map(|m| (m.name.clone(), m)).collect()
+}
+
+#[test]
+fn faces_are_triangulated_and_grouped() {
+    let mesh = parse_obj(QUADS, &red()).unwrap();
+    assert_eq!(mesh.indices.len(), 4);
+    // every corner is used twice within its quad but only stored once per group
+    assert_eq!(mesh.positions.len(), 8);
+    assert_eq!(mesh.positions[mesh.indices[1].y], Vector3::new(1.0, 1.0, 0.0));
+    assert_eq!(mesh.tex_coords[mesh.indices[1].z], Vector2::new(0.0, 1.0));
+
+    let names: Vec<_> = mesh.groups.iter().map(|g| (g.name.as_str(), g.material.as_deref(), g.triangles.clone())).collect();
+    assert_eq!(names, [("left", None, 0..2), ("right", Some("red"), 2..4)]);
+    assert_eq!(mesh.colors[mesh.indices[0].x], Vector3::new(255.0, 255.0, 255.0));
+    assert_eq!(mesh.colors[mesh.indices[3].x], Vector3::new(255.0, 0.0, 0.0));
+    // the right quad has no normals in the file and gets the smooth ones
+    assert!(mesh.normals.iter().all(|n| (n - Vector3::new(0.0, 0.0, 1.0)).norm() < 1e-12));
+
+    let red = &red()["red"];
+    assert_eq!((red.ks, red.ns), (Vector3::new(0.5, 0.5, 0.5), 32.0));
+    assert_eq!(red.diffuse_map.as_deref(), Some(std::path::Path::new("bricks.png")));
+}
+
+#[test]
+fn errors_name_the_line() {
+    let err = |src: &str| parse_obj(src, &HashMap::new()).unwrap_err();
+    assert!(matches!(err("v 0 0 0\nv 1 0\n"), ObjError::Malformed { line: 2, .. }));
+    assert!(matches!(err("v 0 0 0\nvn 0 0 x\n"), ObjError::Malformed { line: 2, .. }));
+    assert!(matches!(err("v 0 0 0\nv 1 0 0\n\nf 1 2\n"), ObjError::Malformed { line: 4, .. }));
+    assert!(matches!(err("v 0 0 0\nusemtl missing\n"), ObjError::Malformed { line: 2, .. }));
+    assert!(matches!(err("v 0 0 0\nv 1 0 0\nf 1 2 3\n"),
+                     ObjError::IndexOutOfRange { line: 3, kind: "position", index: 3, count: 2 }));
+    assert!(matches!(err("v 0 0 0\nf 1 1 -2\n"), ObjError::IndexOutOfRange { line: 2, index: -2, .. }));
+    assert!(matches!(err("v 0 0 0\nf 1/1 1/1 1/1\n"), ObjError::IndexOutOfRange { kind: "texture coordinate", .. }));
+    assert!(matches!(err("v 0 0 0\nf 0 1 1\n"), ObjError::IndexOutOfRange { index: 0, .. }));
+    assert_eq!(err("v 0 0 0\nf 1 1 4\n").to_string(), "line 2: position index 4 is out of range, there are 1");
+    assert!(matches!(parse_mtl("Kd 1 1 1\n"), Err(ObjError::Malformed { line: 1, .. })));
+}
+
+#[test]
+fn material_colors_above_one_are_clamped() {
+    let mtl = parse_mtl("newmtl bright\nKd 1.2 0.5 -0.1\n").unwrap();
+    let materials = mtl.into_iter().map(|m| (m.name.clone(), m)).collect();
+    let mesh = parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl bright\nf 1 2 3\n", &materials).unwrap();
+    assert!(mesh.colors.iter().all(|c| *c == Vector3::new(255.0, 127.5, 0.0)));
+}
+
+#[test]
+fn vertex_colors_in_bytes_are_clamped() {
+    let mesh = parse_obj("v 0 0 0 255 0 0\nv 1 0 0 0 128 0\nv 0 1 0 0.5 0 1\nf 1 2 3\n", &HashMap::new()).unwrap();
+    assert_eq!(mesh.colors, [Vector3::new(255.0, 0.0, 0.0), Vector3::new(0.0, 255.0, 0.0), Vector3::new(127.5, 0.0, 255.0)]);
+    // ready for the rasterizer as they are
+    let mut r = common::setup(10, 10);
+    let buffers = r.load_mesh(&mesh);
+    r.draw(buffers.position, buffers.groups[0], buffers.colors, Primitive::Triangle);
+}
+
+#[test]
+fn loaded_files_render_in_their_material_color() {
+    let dir = std::env::temp_dir().join("games101_obj_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("quad.obj"), "mtllib quad.mtl\nv -1 -1 0\nv 1 -1 0\nv 1 1 0\nv -1 1 0\nusemtl blue\nf 1 2 3 4\n").unwrap();
+    std::fs::write(dir.join("quad.mtl"), "newmtl blue\nKd 0 0 1\nmap_Kd sky.png\n").unwrap();
+    let loaded = load_obj(dir.join("quad.obj"));
+    std::fs::write(dir.join("quad.mtl"), "newmtl blue\nKd 0 0\n").unwrap();
+    let broken = load_obj(dir.join("quad.obj"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(matches!(broken, Err(ObjError::Mtl(_, e)) if matches!(*e, ObjError::Malformed { line: 2, .. })));
+    assert!(matches!(load_obj(dir.join("quad.obj")), Err(ObjError::Io(..))));
+    let (mesh, materials) = loaded.unwrap();
+    assert_eq!(materials["blue"].diffuse_map, Some(dir.join("sky.png")));
+
+    let mut r = common::setup(50, 50);
+    let buffers = r.load_mesh(&mesh);
+    assert!(buffers.normals.is_some() && buffers.tex_coords.is_none());
+    r.draw(buffers.position, buffers.groups[0], buffers.colors, Primitive::Triangle);
+    r.resolve();
+    assert_eq!(r.frame_buffer()[25 * 50 + 25], Vector3::new(0.0, 0.0, 255.0));
+    assert_eq!(r.frame_buffer()[0], Vector3::zeros());
+}
@@ -0,0 +1,100 @@
+use games101::camera::{Camera, Projection, ProjectionMode};
+use games101::rasterizer::{Buffer, Primitive, Rasterizer};
+use games101::utils::get_orthographic_matrix;
+use nalgebra::{Matrix4, Vector3, Vector4};
+
+// view space to NDC
+fn project(p: &Projection, v: Vector3<f64>) -> Vector3<f64> {
+    let clip = p.matrix() * Vector4::new(v.x, v.y, v.z, 1.0);
+    clip.xyz() / clip.w
+}
+
+fn close(a: Vector3<f64>, b: Vector3<f64>) -> bool {
+    (a - b).norm() < 1e-9
+}
+
+#[test]
+fn orthographic_keeps_sizes_at_every_depth() {
+    let p = Projection::new(45.0, 2.0, 1.0, 21.0).with_mode(ProjectionMode::Orthographic { height: 4.0 });
+    for z in [-1.0, -5.0, -21.0] {
+        assert!(close(project(&p, Vector3::new(4.0, 2.0, z)).xy().push(0.0), Vector3::new(1.0, 1.0, 0.0)));
+    }
+    // the same depth mapping as the perspective one at the planes
+    assert!((project(&p, Vector3::new(0.0, 0.0, -1.0)).z + 1.0).abs() < 1e-12);
+    assert!((project(&p, Vector3::new(0.0, 0.0, -21.0)).z - 1.0).abs() < 1e-12);
+    assert!(project(&p, Vector3::new(0.0, 0.0, -11.0)).z.abs() < 1e-12);
+
+    // the textbook matrix for an off-center box
+    let (l, r, b, t, n, f) = (-1.0, 3.0, -2.0, 0.5, 1.0, 21.0);
+    let expected = Matrix4::new(2.0 / (r - l), 0.0, 0.0, -(r + l) / (r - l),
+                                0.0, 2.0 / (t - b), 0.0, -(t + b) / (t - b),
+                                0.0, 0.0, -2.0 / (f - n), -(f + n) / (f - n),
+                                0.0, 0.0, 0.0, 1.0);
+    assert!((get_orthographic_matrix(l, r, b, t, n, f) - expected).norm() < 1e-12);
+}
+
+#[test]
+fn oblique_slides_depth_along_the_angle() {
+    let base = Projection::new(45.0, 1.0, 0.1, 50.0);
+    let cavalier = base.with_mode(ProjectionMode::cavalier(2.0, 5.0));
+    let cabinet = base.with_mode(ProjectionMode::cabinet(2.0, 5.0));
+    // the focus plane is drawn as it is
+    assert!(close(project(&cavalier, Vector3::new(0.5, 0.25, -5.0)).xy().push(0.0), Vector3::new(0.5, 0.25, 0.0)));
+
+    // one unit deeper moves by cos 45 and sin 45 at full length, half that for cabinet
+    let d = 0.5f64.sqrt();
+    let far = Vector3::new(0.0, 0.0, -6.0);
+    assert!(close(project(&cavalier, far).xy().push(0.0), Vector3::new(d, d, 0.0)));
+    assert!(close(project(&cabinet, far).xy().push(0.0), Vector3::new(d, d, 0.0) / 2.0));
+    let angled = base.with_mode(ProjectionMode::Oblique { height: 2.0, angle: 90.0, depth_scale: 1.0, focus: 0.0 });
+    assert!(close(project(&angled, Vector3::new(0.0, 0.0, -0.5)).xy().push(0.0), Vector3::new(0.0, 0.5, 0.0)));
+}
+
+#[test]
+fn off_axis_frustums() {
+    // a centered window is the usual perspective
+    let base = Projection::new(60.0, 1.5, 0.1, 50.0);
+    let top = 30f64.to_radians().tan();
+    let centered = base.with_mode(ProjectionMode::OffAxis { left: -1.5 * top, right: 1.5 * top, bottom: -top, top });
+    assert!((centered.matrix() - base.matrix()).norm() < 1e-12);
+    assert_eq!(base.with_mode(ProjectionMode::stereo(60.0, 1.5, 0.0, 3.0)), centered);
+
+    // both eyes of a stereo pair agree on the convergence plane and disagree in front of it
+    let eye = |offset: f64, p: Vector3<f64>| {
+        let projection = base.with_mode(ProjectionMode::stereo(60.0, 1.5, offset, 4.0));
+        project(&projection, p - Vector3::new(offset, 0.0, 0.0))
+    };
+    let on_plane = Vector3::new(0.3, 0.2, -4.0);
+    assert!(close(eye(-0.1, on_plane), eye(0.1, on_plane)));
+    let near = Vector3::new(0.3, 0.2, -2.0);
+    assert!(eye(-0.1, near).x > eye(0.1, near).x);
+    // the window is shifted, not turned, so vertical positions always agree
+    assert!((eye(-0.1, near).y - eye(0.1, near).y).abs() < 1e-12);
+}
+
+#[test]
+fn the_camera_switches_modes() {
+    const SIZE: u64 = 40;
+    let mut r = Rasterizer::new(SIZE, SIZE);
+    // a 2x2 square, far enough that perspective draws it smaller than orthographic
+    let pos_id = r.load_position(&[Vector3::new(-1.0, -1.0, -5.0), Vector3::new(1.0, -1.0, -5.0),
+                                   Vector3::new(1.0, 1.0, -5.0), Vector3::new(-1.0, 1.0, -5.0)]);
+    let ind_id = r.load_indices(&[Vector3::new(0, 1, 2), Vector3::new(0, 2, 3)]);
+    let col_id = r.load_colors(&[Vector3::new(255.0, 255.0, 255.0); 4]);
+    let mut covered = |camera: &Camera, z: f64| {
+        r.clear(Buffer::Both);
+        r.set_model(Matrix4::new_translation(&Vector3::new(0.0, 0.0, z)));
+        r.set_camera(camera);
+        r.draw(pos_id, ind_id, col_id, Primitive::Triangle);
+        r.resolve();
+        r.frame_buffer().iter().map(|c| c.x / 255.0).sum::<f64>()
+    };
+    let mut camera = Camera { mode: ProjectionMode::Orthographic { height: 4.0 }, ..Default::default() };
+    // half the screen each way, whatever the distance
+    let ortho = covered(&camera, 0.0);
+    assert!((ortho / (SIZE * SIZE / 4) as f64 - 1.0).abs() < 0.05, "{}", ortho);
+    assert_eq!(covered(&camera, -20.0), ortho);
+    camera.mode = ProjectionMode::Perspective;
+    assert!(covered(&camera, 0.0) < ortho / 2.0);
+    assert!(covered(&camera, -20.0) < covered(&camera, 0.0) / 2.0);
+}
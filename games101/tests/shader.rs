@@ -0,0 +1,154 @@
+use std::cell::Cell;
+use std::ops::{Add, Mul};
+use games101::rasterizer::{Primitive, Rasterizer};
+use games101::shader::{FragmentInput, VertexOutput, WithDerivatives};
+use nalgebra::{Vector3, Vector4};
+
+mod common;
+
+const SIZE: u64 = 120;
+
+// the LAB2 triangles
+struct Scene {
+    pos: Vec<Vector3<f64>>,
+    ind: Vec<Vector3<usize>>,
+    col: Vec<Vector3<f64>>,
+}
+
+fn scene() -> Scene {
+    let pos = vec![Vector3::new(2.0, 0.0, -2.0),
+                   Vector3::new(0.0, 2.0, -2.0),
+                   Vector3::new(-2.0, 0.0, -2.0),
+                   Vector3::new(3.5, -1.0, -5.0),
+                   Vector3::new(2.5, 1.5, -5.0),
+                   Vector3::new(-1.0, 0.5, -1.0)];
+    let ind = vec![Vector3::new(0, 1, 2), Vector3::new(3, 4, 5)];
+    let col = vec![Vector3::new(217.0, 238.0, 185.0),
+                   Vector3::new(217.0, 238.0, 185.0),
+                   Vector3::new(217.0, 238.0, 185.0),
+                   Vector3::new(185.0, 217.0, 238.0),
+                   Vector3::new(185.0, 217.0, 238.0),
+                   Vector3::new(185.0, 217.0, 238.0)];
+    Scene { pos, ind, col }
+}
+
+fn setup() -> Rasterizer {
+    common::setup(SIZE, SIZE)
+}
+
+#[derive(Clone, Copy)]
+struct Vertex {
+    pos: Vector3<f64>,
+    color: Vector3<f64>,
+}
+
+// a typed varying, the rasterizer only needs to add and scale it
+#[derive(Clone, Copy)]
+struct Varyings {
+    color: Vector3<f64>,
+    height: f64,
+}
+
+impl Add for Varyings {
+    type Output = Varyings;
+    fn add(self, o: Varyings) -> Varyings {
+        Varyings { color: self.color + o.color, height: self.height + o.height }
+    }
+}
+
+impl Mul<f64> for Varyings {
+    type Output = Varyings;
+    fn mul(self, k: f64) -> Varyings {
+        Varyings { color: self.color * k, height: self.height * k }
+    }
+}
+
+#[test]
+fn shaders_can_reproduce_the_fixed_pipeline() {
+    let Scene { pos, ind, col } = scene();
+    let mut fixed = setup();
+    let (pos_id, ind_id, col_id) = common::load(&mut fixed, &pos, &ind, &col);
+    fixed.draw(pos_id, ind_id, col_id, Primitive::Triangle);
+    fixed.resolve();
+
+    let mut shaded = setup();
+    let mvp = shaded.mvp();
+    let vertices: Vec<Vertex> = pos.iter().zip(&col).map(|(&pos, &color)| Vertex { pos, color }).collect();
+    let calls = Cell::new(0);
+    let vs = |v: &Vertex| {
+        calls.set(calls.get() + 1);
+        VertexOutput { position: mvp * Vector4::new(v.pos.x, v.pos.y, v.pos.z, 1.0), varying: Varyings { color: v.color, height: v.pos.y } }
+    };
+    let fs = |f: &FragmentInput<Varyings>| Some(f.varying.color);
+    shaded.draw_shaded(&vertices, &ind, &vs, &fs);
+    shaded.resolve();
+
+    // once per vertex, not per corner
+    assert_eq!(calls.get(), vertices.len());
+    assert_eq!(shaded.stats(), fixed.stats());
+    let diff = fixed.frame_buffer().iter().zip(shaded.frame_buffer()).map(|(a, b)| (a - b).norm()).fold(0.0, f64::max);
+    assert!(diff < 1e-9, "{}", diff);
+}
+
+#[test]
+fn fragments_see_interpolated_varyings_and_can_be_discarded() {
+    let Scene { pos, ind, .. } = scene();
+    let mut r = setup();
+    let mvp = r.mvp();
+    let vs = |p: &Vector3<f64>| VertexOutput { position: mvp * Vector4::new(p.x, p.y, p.z, 1.0), varying: p.y };
+    // the height is interpolated perspective correct, everything above 1 is cut away
+    let fs = |f: &FragmentInput<f64>| if f.varying > 1.0 { None } else { Some(Vector3::new(255.0, 255.0, 255.0) * (f.varying + 1.0) / 2.0) };
+    r.draw_shaded(&pos[..3], &ind[..1], &vs, &fs);
+    r.resolve();
+
+    // rows from the top, the tip of the triangle is gone and the rest brightens towards it
+    let row = |y: u64| r.frame_buffer()[(y * SIZE + SIZE / 2) as usize].x;
+    let lit: Vec<u64> = (0..SIZE).filter(|&y| row(y) > 0.0).collect();
+    let (top, bottom) = (lit[0], *lit.last().unwrap());
+    assert!(bottom - top < 25, "{} {}", top, bottom);
+    assert!(row(top + 1) > row(bottom - 1));
+    assert!(row(top + 1) <= 255.0 + 1e-9);
+}
+
+#[test]
+fn fragments_know_their_facing() {
+    let mut r = setup();
+    let mvp = r.mvp();
+    let pos = vec![Vector3::new(-2.0, 0.0, -2.0), Vector3::new(-0.5, 0.0, -2.0), Vector3::new(-1.0, 1.5, -2.0),
+                   Vector3::new(0.5, 0.0, -2.0), Vector3::new(1.0, 1.5, -2.0), Vector3::new(2.0, 0.0, -2.0)];
+    let vs = |p: &Vector3<f64>| VertexOutput { position: mvp * Vector4::new(p.x, p.y, p.z, 1.0), varying: 0.0 };
+    let fs = |f: &FragmentInput<f64>| Some(if f.front_facing { Vector3::new(255.0, 0.0, 0.0) } else { Vector3::new(0.0, 0.0, 255.0) });
+    r.draw_shaded(&pos, &[Vector3::new(0, 1, 2), Vector3::new(3, 4, 5)], &vs, &fs);
+    r.resolve();
+
+    let y = SIZE / 2 - 8;
+    let at = |x: u64| r.frame_buffer()[(y * SIZE + x) as usize];
+    assert!(at(SIZE / 2 - 18).x > 0.0 && at(SIZE / 2 - 18).z == 0.0);
+    assert!(at(SIZE / 2 + 18).z > 0.0 && at(SIZE / 2 + 18).x == 0.0);
+}
+
+#[test]
+fn derivatives_are_only_worked_out_when_asked_for() {
+    // x in world space across a triangle facing the camera 7 units away
+    let pos = [Vector3::new(-1.0, -1.0, -2.0), Vector3::new(1.0, -1.0, -2.0), Vector3::new(0.0, 1.0, -2.0)];
+    let derivatives = |needed: bool| {
+        let mut r = setup();
+        let mvp = r.mvp();
+        let vs = |p: &Vector3<f64>| VertexOutput { position: mvp * Vector4::new(p.x, p.y, p.z, 1.0), varying: p.x };
+        let seen = Cell::new((0.0, 0.0));
+        let fs = |f: &FragmentInput<f64>| {
+            seen.set((f.ddx, f.ddy));
+            Some(Vector3::zeros())
+        };
+        if needed {
+            r.draw_shaded(&pos, &[Vector3::new(0, 1, 2)], &vs, &WithDerivatives(fs));
+        } else {
+            r.draw_shaded(&pos, &[Vector3::new(0, 1, 2)], &vs, &fs);
+        }
+        seen.get()
+    };
+    assert_eq!(derivatives(false), (0.0, 0.0));
+    let (ddx, ddy) = derivatives(true);
+    let pixel = 2.0 * 7.0 * 22.5f64.to_radians().tan() / SIZE as f64;
+    assert!((ddx - pixel).abs() < 1e-9 && ddy.abs() < 1e-9, "{} {}", ddx, ddy);
+}
@@ -0,0 +1,48 @@
+use games101::msaa::{ResolveFilter, SamplePattern};
+use games101::rasterizer::{AntiAliasing, Primitive, Rasterizer};
+use games101::utils::*;
+use nalgebra::Vector3;
+
+mod common;
+
+fn render(mode: AntiAliasing) -> Rasterizer {
+    let mut r = Rasterizer::new(120, 80);
+    r.set_antialiasing(mode);
+    let pos = vec![Vector3::new(2.0, 0.0, -2.0),
+                   Vector3::new(0.0, 2.0, -2.0),
+                   Vector3::new(-2.0, 0.0, -2.0),
+                   Vector3::new(3.5, -1.0, -5.0),
+                   Vector3::new(2.5, 1.5, -5.0),
+                   Vector3::new(-1.0, 0.5, -5.0)];
+    let ind = vec![Vector3::new(0, 1, 2), Vector3::new(3, 4, 5)];
+    let cols = vec![Vector3::new(255.0, 0.0, 0.0), Vector3::new(0.0, 255.0, 0.0), Vector3::new(0.0, 0.0, 255.0),
+                    Vector3::new(185.0, 217.0, 238.0), Vector3::new(185.0, 217.0, 238.0), Vector3::new(185.0, 217.0, 238.0)];
+    let (pos_id, ind_id, col_id) = common::load(&mut r, &pos, &ind, &cols);
+
+    common::reset(&mut r);
+    r.set_model(get_model_matrix(20.0));
+    r.draw(pos_id, ind_id, col_id, Primitive::Triangle);
+    r.resolve();
+    r
+}
+
+#[test]
+fn ssaa_keeps_the_output_size() {
+    let r = render(AntiAliasing::Ssaa { factor: 3, filter: ResolveFilter::Tent });
+    assert_eq!((r.width(), r.height()), (120, 80));
+    assert_eq!(r.frame_buffer().len(), 120 * 80);
+    assert_eq!(r.depth_buffer().len(), 360 * 240);
+    assert_eq!(r.aspect_ratio(), 1.5);
+}
+
+#[test]
+fn box_filtered_ssaa_matches_msaa_on_the_same_grid() {
+    // 4x SSAA puts one sample in the center of each of 4x4 sub-pixels, as the 16 sample ordered grid does
+    let ssaa = render(AntiAliasing::Ssaa { factor: 4, filter: ResolveFilter::Box });
+    let msaa = render(AntiAliasing::Msaa { samples: 16, pattern: SamplePattern::OrderedGrid, filter: ResolveFilter::Box });
+    let none = render(AntiAliasing::None);
+    let max_diff = |a: &Rasterizer, b: &Rasterizer| a.frame_buffer().iter().zip(b.frame_buffer())
+        .map(|(a, b)| (a - b).abs().max()).fold(0.0, f64::max);
+    assert!(max_diff(&ssaa, &msaa) < 1e-6, "{}", max_diff(&ssaa, &msaa));
+    assert!(max_diff(&ssaa, &none) > 50.0);
+}
@@ -0,0 +1,74 @@
+use games101::msaa::SamplePattern;
+use games101::rasterizer::{ColBufId, IndBufId, PosBufId, Primitive, Rasterizer};
+use games101::taa::{jitter_offset, TaaSettings};
+use nalgebra::{Matrix4, Vector3};
+
+mod common;
+use common::SIZE;
+
+const COLOR: [f64; 3] = [200.0, 100.0, 50.0];
+
+struct Scene {
+    r: Rasterizer,
+    ids: (PosBufId, IndBufId, ColBufId),
+}
+
+impl Scene {
+    fn new(settings: TaaSettings) -> Self {
+        let mut r = Rasterizer::new(SIZE, SIZE);
+        r.set_msaa(1, SamplePattern::OrderedGrid);
+        r.set_taa(Some(settings));
+        let pos = vec![Vector3::new(-1.0, -1.0, -2.0),
+                       Vector3::new(1.0, -1.0, -2.0),
+                       Vector3::new(0.0, 1.0, -2.0)];
+        let ids = common::load(&mut r, &pos, &[Vector3::new(0, 1, 2)], &[Vector3::new(COLOR[0], COLOR[1], COLOR[2]); 3]);
+        Scene { r, ids }
+    }
+
+    // one frame with the triangle moved by dx along x in world space
+    fn frame(&mut self, dx: f64) {
+        let r = &mut self.r;
+        common::reset(r);
+        r.set_model(Matrix4::new_translation(&Vector3::new(dx, 0.0, 0.0)));
+        r.draw(self.ids.0, self.ids.1, self.ids.2, Primitive::Triangle);
+        r.resolve();
+    }
+}
+
+#[test]
+fn jitter_stays_inside_the_pixel_and_repeats() {
+    for i in 0..8 {
+        let j = jitter_offset(i, 8);
+        assert!(j.x.abs() < 0.5 && j.y.abs() < 0.5);
+        assert_eq!(j, jitter_offset(i + 8, 8));
+    }
+    assert_ne!(jitter_offset(0, 8), jitter_offset(1, 8));
+}
+
+#[test]
+fn a_still_scene_converges_to_soft_edges() {
+    let mut scene = Scene::new(TaaSettings::default());
+    for _ in 0..32 {
+        scene.frame(0.0);
+    }
+    let fb = scene.r.frame_buffer();
+    assert!((fb[(SIZE / 2 * SIZE + SIZE / 2) as usize].x - COLOR[0]).abs() < 1e-6);
+    let partial = fb.iter().filter(|c| c.x > 1.0 && c.x < COLOR[0] - 1.0).count();
+    assert!(partial > 50, "only {} edge pixels were blended", partial);
+}
+
+#[test]
+fn moving_geometry_is_reprojected() {
+    // without the clamp only the motion vectors keep the background out of the history
+    let mut scene = Scene::new(TaaSettings { clamp: false, ..Default::default() });
+    for i in 0..8 {
+        scene.frame(0.1 * i as f64);
+    }
+
+    // the triangle moves right by about 1.3 pixels a frame, pixel 62 was only covered for
+    // the last two frames
+    let ind = (SIZE / 2 * SIZE + 62) as usize;
+    let v = scene.r.velocity_buffer()[ind];
+    assert!(v.x < -1.0 && v.y.abs() < 1e-6, "{:?}", v);
+    assert!((scene.r.frame_buffer()[ind].x - COLOR[0]).abs() < 1e-6, "{:?}", scene.r.frame_buffer()[ind]);
+}
@@ -0,0 +1,114 @@
+use games101::camera::Projection;
+use games101::msaa::SamplePattern;
+use games101::rasterizer::{Buffer, Rasterizer};
+use games101::texture::{Texture, TextureFilter, Wrap};
+use games101::utils::*;
+use image::{Rgb, RgbImage};
+use nalgebra::{Vector2, Vector3};
+
+fn gray(c: f64) -> Vector3<f64> {
+    Vector3::new(c, c, c)
+}
+
+// one texel wide black and white squares
+fn checker(size: usize) -> Texture {
+    Texture::new(size, size, (0..size * size).map(|i| gray(if (i % size + i / size).is_multiple_of(2) { 0.0 } else { 255.0 })).collect())
+}
+
+#[test]
+fn wrap_modes_and_border() {
+    let mut t = Texture::new(2, 1, vec![gray(0.0), gray(255.0)]);
+    t.filter = TextureFilter::Nearest;
+    t.border = Vector3::new(1.0, 2.0, 3.0);
+    let at = |t: &Texture, u: f64| t.sample(Vector2::new(u, 0.5));
+    assert_eq!(at(&t, 0.25), gray(0.0));
+    assert_eq!(at(&t, 0.75), gray(255.0));
+
+    let expect = [(Wrap::Repeat, gray(0.0), gray(0.0)),
+                  (Wrap::Clamp, gray(255.0), gray(0.0)),
+                  (Wrap::Mirror, gray(255.0), gray(255.0)),
+                  (Wrap::Border, Vector3::new(1.0, 2.0, 3.0), Vector3::new(1.0, 2.0, 3.0))];
+    for (wrap, right, left) in expect {
+        t.wrap = wrap;
+        assert_eq!(at(&t, 1.25), right, "{:?}", wrap);
+        assert_eq!(at(&t, -0.75), left, "{:?}", wrap);
+    }
+
+    // bilinear blends between texel centers and into the border
+    t.filter = TextureFilter::Bilinear;
+    t.wrap = Wrap::Clamp;
+    assert_eq!(at(&t, 0.5), gray(127.5));
+    assert_eq!(at(&t, 0.25), gray(0.0));
+    t.wrap = Wrap::Repeat;
+    assert_eq!(at(&t, 0.0), gray(127.5));
+}
+
+#[test]
+fn images_are_loaded_with_v_pointing_up() {
+    let mut img = RgbImage::new(2, 2);
+    img.put_pixel(0, 0, Rgb([255, 0, 0]));
+    img.put_pixel(1, 1, Rgb([0, 0, 255]));
+    let path = std::env::temp_dir().join("games101_texture_test.png");
+    img.save(&path).unwrap();
+    let mut t = Texture::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    t.filter = TextureFilter::Nearest;
+
+    assert_eq!((t.width(), t.height()), (2, 2));
+    assert_eq!(t.sample(Vector2::new(0.25, 0.75)), Vector3::new(255.0, 0.0, 0.0));
+    assert_eq!(t.sample(Vector2::new(0.75, 0.25)), Vector3::new(0.0, 0.0, 255.0));
+    assert!(Texture::load(std::env::temp_dir().join("games101_missing_texture.png")).is_err());
+}
+
+#[test]
+fn mipmaps_average_down_to_one_texel() {
+    let mut t = checker(8);
+    t.generate_mipmaps();
+    assert_eq!(t.levels(), 4);
+    let uv = Vector2::new(0.3, 0.6);
+    assert_eq!(t.sample_level(uv, 3.0), gray(127.5));
+    assert_eq!(t.sample_level(uv, 1.0), gray(127.5));
+    assert!((t.sample_level(uv, 0.5) - t.sample_level(uv, 0.0)).norm() > 1.0);
+
+    // a pixel that covers one texel stays on the base level, four texels is two levels down
+    let texel = 1.0 / 8.0;
+    assert_eq!(t.lod(Vector2::new(texel, 0.0), Vector2::new(0.0, texel)), 0.0);
+    assert!((t.lod(Vector2::new(4.0 * texel, 0.0), Vector2::new(0.0, texel)) - 2.0).abs() < 1e-12);
+}
+
+// a floor going off into the distance, tiled many times
+fn render_floor(filter: TextureFilter) -> Vec<Vector3<f64>> {
+    const SIZE: u64 = 100;
+    let mut r = Rasterizer::new(SIZE, SIZE);
+    // one sample per pixel so only the texture filter is left to fight aliasing
+    r.set_msaa(1, SamplePattern::OrderedGrid);
+    r.clear(Buffer::Both);
+    r.set_model(get_model_matrix(0.0));
+    r.set_view(get_view_matrix(Vector3::new(0.0, 1.0, 5.0)));
+    r.set_projection_params(Projection::new(45.0, r.aspect_ratio(), 0.1, 200.0));
+    let pos = [Vector3::new(-20.0, 0.0, 4.0), Vector3::new(20.0, 0.0, 4.0),
+               Vector3::new(20.0, 0.0, -150.0), Vector3::new(-20.0, 0.0, -150.0)];
+    let uv = [Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(10.0, 40.0), Vector2::new(0.0, 40.0)];
+    let pos_id = r.load_position(&pos);
+    let ind_id = r.load_indices(&[Vector3::new(0, 1, 2), Vector3::new(0, 2, 3)]);
+    let tex_id = r.load_tex_coords(&uv);
+    let mut t = checker(16);
+    t.filter = filter;
+    t.generate_mipmaps();
+    r.draw_textured(pos_id, ind_id, tex_id, &t);
+    r.resolve();
+    // the rows just below the horizon
+    let horizon = r.frame_buffer().iter().position(|c| c.x > 0.0).unwrap() as u64 / SIZE;
+    r.frame_buffer()[((horizon + 2) * SIZE) as usize..((horizon + 6) * SIZE) as usize].to_vec()
+}
+
+#[test]
+fn trilinear_filtering_removes_aliasing_in_the_distance() {
+    let spread = |pixels: &[Vector3<f64>]| {
+        let mean = pixels.iter().map(|c| c.x).sum::<f64>() / pixels.len() as f64;
+        (pixels.iter().map(|c| (c.x - mean).powi(2)).sum::<f64>() / pixels.len() as f64).sqrt()
+    };
+    let bilinear = spread(&render_floor(TextureFilter::Bilinear));
+    let trilinear = spread(&render_floor(TextureFilter::Trilinear));
+    assert!(trilinear < bilinear / 3.0, "{} {}", trilinear, bilinear);
+}
@@ -0,0 +1,82 @@
+use games101::transform::Transform;
+use games101::utils::*;
+use nalgebra::{Matrix4, Rotation3, Unit, UnitQuaternion, Vector3};
+use proptest::prelude::*;
+
+fn vector(range: f64) -> impl Strategy<Value = Vector3<f64>> {
+    (-range..range, -range..range, -range..range).prop_map(|(x, y, z)| Vector3::new(x, y, z))
+}
+
+fn axis() -> impl Strategy<Value = Vector3<f64>> {
+    vector(10.0).prop_filter("axis too short", |a| a.norm() > 1e-3)
+}
+
+fn close(a: &Matrix4<f64>, b: &Matrix4<f64>) -> bool {
+    (a - b).norm() < 1e-9
+}
+
+#[test]
+fn steps_apply_in_the_order_they_are_chained() {
+    let t = Transform::scaling(Vector3::new(2.0, 2.0, 2.0))
+        .rotate(Vector3::z(), 90.0)
+        .translate(Vector3::new(1.0, 0.0, 0.0));
+    assert!((t.transform_point(Vector3::new(1.0, 0.0, 0.0)) - Vector3::new(1.0, 2.0, 0.0)).norm() < 1e-12);
+    assert!((t.transform_vector(Vector3::new(1.0, 0.0, 0.0)) - Vector3::new(0.0, 2.0, 0.0)).norm() < 1e-12);
+    let by_hand = Transform::translation(Vector3::new(1.0, 0.0, 0.0)) * Transform::rotation(Vector3::z(), 90.0)
+        * Transform::scaling(Vector3::new(2.0, 2.0, 2.0));
+    assert!(close(&t.matrix(), &by_hand.matrix()));
+
+    // the rotating point stays put, the rest goes around it
+    let about = Transform::rotation_about(Vector3::new(1.0, 1.0, 0.0), Vector3::z(), 180.0);
+    assert!((about.transform_point(Vector3::new(1.0, 1.0, 0.0)) - Vector3::new(1.0, 1.0, 0.0)).norm() < 1e-12);
+    assert!((about.transform_point(Vector3::zeros()) - Vector3::new(2.0, 2.0, 0.0)).norm() < 1e-12);
+
+    // flattened space has no inverse, the zero axis is no rotation
+    assert!(Transform::scaling(Vector3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    assert_eq!(Transform::rotation(Vector3::zeros(), 30.0), Transform::identity());
+}
+
+proptest! {
+    #[test]
+    fn rotations_match_nalgebra(axis in axis(), angle in -720.0..720.0f64, roll in -180.0..180.0f64,
+                                pitch in -90.0..90.0f64, yaw in -180.0..180.0f64) {
+        let expected = Rotation3::from_axis_angle(&Unit::new_normalize(axis), angle.to_radians()).to_homogeneous();
+        prop_assert!(close(&Transform::rotation(axis, angle).matrix(), &expected));
+        // get_rotation normalizes the axis now
+        prop_assert!(close(&get_rotation(axis, angle), &expected));
+
+        let euler = Rotation3::from_euler_angles(roll.to_radians(), pitch.to_radians(), yaw.to_radians());
+        prop_assert!(close(&Transform::euler(roll, pitch, yaw).matrix(), &euler.to_homogeneous()));
+
+        let q = UnitQuaternion::from_axis_angle(&Unit::new_normalize(axis), angle.to_radians());
+        prop_assert!(close(&Transform::from_quaternion(&q).matrix(), &expected));
+        prop_assert!(close(&Transform::from(q).matrix(), &Transform::from(q.to_rotation_matrix()).matrix()));
+    }
+
+    #[test]
+    fn inverse_undoes_the_transform(scale in vector(4.0).prop_filter("too flat", |s| s.iter().all(|x| x.abs() > 0.1)),
+                                    axis in axis(), angle in -360.0..360.0f64, offset in vector(10.0),
+                                    shear in vector(1.0), p in vector(10.0)) {
+        let t = Transform::scaling(scale).shear(shear.x, shear.y, 0.0, shear.z, 0.0, 0.0)
+            .rotate(axis, angle).translate(offset);
+        let inv = t.inverse().unwrap();
+        prop_assert!(close(&(t * inv).matrix(), &Matrix4::identity()));
+        prop_assert!((inv.transform_point(t.transform_point(p)) - p).norm() < 1e-8);
+        prop_assert!(close(&t.then(&inv).matrix(), &Matrix4::identity()));
+    }
+
+    #[test]
+    fn normals_stay_perpendicular(scale in vector(4.0).prop_filter("too flat", |s| s.iter().all(|x| x.abs() > 0.1)),
+                                  shear in vector(2.0), axis in axis(), angle in -360.0..360.0f64,
+                                  a in vector(1.0), b in vector(1.0)) {
+        prop_assume!(a.cross(&b).norm() > 1e-3);
+        let t = Transform::rotation(axis, angle).shear(shear.x, 0.0, shear.y, 0.0, shear.z, 0.0).scale(scale);
+        // a and b span a plane, the normal of the transformed plane is the transformed normal
+        let n = t.transform_normal(a.cross(&b));
+        prop_assert!(n.dot(&t.transform_vector(a)).abs() < 1e-9 * t.transform_vector(a).norm().max(1.0));
+        prop_assert!(n.dot(&t.transform_vector(b)).abs() < 1e-9 * t.transform_vector(b).norm().max(1.0));
+        // for a pure rotation it is the rotation itself
+        let r = Transform::rotation(axis, angle);
+        prop_assert!((r.normal_matrix() - r.matrix().fixed_view::<3, 3>(0, 0)).norm() < 1e-12);
+    }
+}
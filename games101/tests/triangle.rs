@@ -0,0 +1,48 @@
+use games101::msaa::SamplePattern;
+use games101::rasterizer::Primitive;
+use games101::triangle::Triangle;
+use nalgebra::{Vector2, Vector3, Vector4};
+
+mod common;
+
+#[test]
+fn colors_out_of_range_are_clamped() {
+    let mut t = Triangle::new();
+    t.set_color(0, 300.0, -5.0, 51.0);
+    assert_eq!(t.color[0], Vector3::new(1.0, 0.0, 0.2));
+    assert_eq!(t.get_color(), Vector3::new(255.0, 0.0, 51.0));
+}
+
+#[test]
+fn colors_are_interpolated_perspective_correct() {
+    const SIZE: u64 = 100;
+    let mut r = common::setup(SIZE, SIZE);
+    // one sample in the middle of every pixel
+    r.set_msaa(1, SamplePattern::OrderedGrid);
+    common::reset(&mut r);
+    // a floor running away from the camera, red in front and blue far back
+    let pos = [Vector3::new(-1.0, -1.0, 2.0), Vector3::new(1.0, -1.0, 2.0), Vector3::new(0.0, -1.0, -30.0)];
+    let col = [Vector3::new(255.0, 0.0, 0.0), Vector3::new(255.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 255.0)];
+    let (pos_id, ind_id, col_id) = common::load(&mut r, &pos, &[Vector3::new(0, 1, 2)], &col);
+    r.draw(pos_id, ind_id, col_id, Primitive::Triangle);
+    r.resolve();
+
+    // the same screen positions and w the rasterizer works with, y pointing up
+    let clip: Vec<Vector4<f64>> = pos.iter().map(|p| r.mvp() * p.push(1.0)).collect();
+    let screen: Vec<Vector2<f64>> = clip.iter().map(|c| (c.xy() / c.w).add_scalar(1.0) * (SIZE as f64 / 2.0)).collect();
+    // halfway up the triangle on screen
+    let y = ((screen[0].y + screen[2].y) / 2.0).floor();
+    let p = Vector2::new(SIZE as f64 / 2.0 + 0.5, y + 0.5);
+    let area = |a: Vector2<f64>, b: Vector2<f64>, c: Vector2<f64>| (b - a).perp(&(c - a));
+    let total = area(screen[0], screen[1], screen[2]);
+    let bary = [area(p, screen[1], screen[2]) / total, area(screen[0], p, screen[2]) / total, area(screen[0], screen[1], p) / total];
+
+    let linear: Vector3<f64> = (0..3).map(|i| col[i] * bary[i]).sum();
+    let weights: Vec<f64> = (0..3).map(|i| bary[i] / clip[i].w).collect();
+    let correct: Vector3<f64> = (0..3).map(|i| col[i] * weights[i]).sum::<Vector3<f64>>() / weights.iter().sum::<f64>();
+
+    let got = common::pixel(&r, SIZE / 2, SIZE - 1 - y as u64);
+    assert!((got - correct).norm() < 1e-6, "{:?} instead of {:?}", got, correct);
+    // the far end of the floor is squeezed into a few rows, so halfway up the screen is still mostly red
+    assert!(correct.x > linear.x + 50.0, "{:?} vs {:?}", correct, linear);
+}
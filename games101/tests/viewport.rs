@@ -0,0 +1,65 @@
+use games101::rasterizer::{Primitive, Rasterizer};
+use games101::utils::parse_size;
+use nalgebra::Vector3;
+
+mod common;
+use common::fill;
+
+// a 2x2 white square at z = -2, seen from 7 units away
+fn render(width: u64, height: u64) -> Rasterizer {
+    let mut r = common::setup(width, height);
+    let (a, b, c, d) = (Vector3::new(-1.0, -1.0, -2.0), Vector3::new(1.0, -1.0, -2.0),
+                        Vector3::new(1.0, 1.0, -2.0), Vector3::new(-1.0, 1.0, -2.0));
+    fill(&mut r, &[a, b, c], Vector3::new(255.0, 255.0, 255.0), Primitive::Triangle);
+    fill(&mut r, &[a, c, d], Vector3::new(255.0, 255.0, 255.0), Primitive::Triangle);
+    r.resolve();
+    r
+}
+
+// (x0, x1, y0, y1) of the pixels that are at least half covered
+fn bounds(r: &Rasterizer) -> (u64, u64, u64, u64) {
+    let lit: Vec<(u64, u64)> = (0..r.height()).flat_map(|y| (0..r.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| common::pixel(r, x, y).x >= 127.5)
+        .collect();
+    (lit.iter().map(|p| p.0).min().unwrap(), lit.iter().map(|p| p.0).max().unwrap() + 1,
+     lit.iter().map(|p| p.1).min().unwrap(), lit.iter().map(|p| p.1).max().unwrap() + 1)
+}
+
+#[test]
+fn squares_stay_square_on_wide_and_tall_screens() {
+    for (width, height) in [(320, 180), (180, 320)] {
+        let r = render(width, height);
+        assert_eq!(r.aspect_ratio(), width as f64 / height as f64);
+        assert_eq!(r.frame_buffer().len() as u64, width * height);
+
+        // the field of view is vertical, so the size in pixels only depends on the height
+        let side = height as f64 / (7.0 * 22.5f64.to_radians().tan());
+        let (x0, x1, y0, y1) = bounds(&r);
+        let (w, h) = ((x1 - x0) as f64, (y1 - y0) as f64);
+        assert!((w - side).abs() <= 1.0 && (h - side).abs() <= 1.0, "{}x{}: {}x{} instead of {}", width, height, w, h, side);
+        assert!(((x0 + x1) as f64 / 2.0 - width as f64 / 2.0).abs() <= 0.5);
+        assert!(((y0 + y1) as f64 / 2.0 - height as f64 / 2.0).abs() <= 0.5);
+    }
+}
+
+#[test]
+fn triangles_larger_than_a_narrow_screen_are_cut_at_its_edges() {
+    for (width, height) in [(320, 180), (180, 320)] {
+        let mut r = common::setup(width, height);
+        // wider and taller than what either screen shows
+        fill(&mut r, &[Vector3::new(-30.0, -20.0, -2.0), Vector3::new(30.0, -20.0, -2.0), Vector3::new(0.0, 40.0, -2.0)],
+             Vector3::new(0.0, 255.0, 0.0), Primitive::Triangle);
+        r.resolve();
+        // the clipped corners interpolate the color, so allow for rounding
+        let green = Vector3::new(0.0, 255.0, 0.0);
+        assert!(r.frame_buffer().iter().all(|c| (c - green).norm() < 1e-9), "{}x{}", width, height);
+    }
+}
+
+#[test]
+fn size_arguments_must_be_positive_integers() {
+    assert_eq!(parse_size("320", "180"), Ok((320, 180)));
+    for (width, height) in [("320", "abc"), ("-1", "180"), ("0", "180"), ("32.5", "180")] {
+        assert!(parse_size(width, height).is_err(), "{} {}", width, height);
+    }
+}
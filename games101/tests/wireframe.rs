@@ -0,0 +1,72 @@
+use games101::clip::clip_line_to_rect;
+use games101::line::LineStyle;
+use games101::rasterizer::{Primitive, Rasterizer};
+use nalgebra::{Vector2, Vector3};
+
+mod common;
+use common::fill;
+
+const SIZE: u64 = 200;
+
+fn setup() -> Rasterizer {
+    common::setup(SIZE, SIZE)
+}
+
+// green pixels in columns [x0, x1)
+fn green(r: &Rasterizer, x0: u64, x1: u64) -> usize {
+    r.frame_buffer().iter().enumerate()
+        .filter(|(i, c)| (x0..x1).contains(&(*i as u64 % SIZE)) && c.y > 0.0 && c.x == 0.0)
+        .count()
+}
+
+#[test]
+fn lines_are_clipped_to_the_screen_before_stepping() {
+    let (lo, hi) = (Vector2::new(0.0, 0.0), Vector2::new(99.0, 99.0));
+    let (t0, t1) = clip_line_to_rect(Vector2::new(-1e6, 50.0), Vector2::new(1e6, 50.0), lo, hi).unwrap();
+    assert!((t0 * 2e6 - 1e6).abs() < 1e-6 && (t1 * 2e6 - 1e6 - 99.0).abs() < 1e-6);
+
+    assert_eq!(clip_line_to_rect(Vector2::new(10.0, 10.0), Vector2::new(20.0, 80.0), lo, hi), Some((0.0, 1.0)));
+    assert_eq!(clip_line_to_rect(Vector2::new(-10.0, 50.0), Vector2::new(50.0, 150.0), lo, hi).map(|_| ()), Some(()));
+    // crosses the corner region without entering
+    assert_eq!(clip_line_to_rect(Vector2::new(-10.0, 90.0), Vector2::new(20.0, 130.0), lo, hi), None);
+    assert_eq!(clip_line_to_rect(Vector2::new(-5.0, -5.0), Vector2::new(-1.0, 200.0), lo, hi), None);
+}
+
+#[test]
+fn lines_are_depth_tested_against_triangles() {
+    let mut r = setup();
+    let wall = [Vector3::new(-10.0, -10.0, -2.0), Vector3::new(10.0, -10.0, -2.0), Vector3::new(0.0, 10.0, -2.0)];
+    fill(&mut r, &wall, Vector3::new(255.0, 0.0, 0.0), Primitive::Triangle);
+    // passes through the wall in the middle of the screen
+    r.draw_line_strip(&[Vector3::new(-2.0, 0.3, -1.0), Vector3::new(2.0, 0.3, -3.0)], false);
+    r.resolve();
+
+    assert!(green(&r, 0, SIZE / 2 - 5) > 20);
+    assert_eq!(green(&r, SIZE / 2 + 5, SIZE), 0);
+}
+
+#[test]
+fn overlay_edges_do_not_z_fight_with_their_faces() {
+    // steep in depth so the faces and their edges disagree from pixel to pixel
+    let slanted = [Vector3::new(-2.0, -1.5, 0.0), Vector3::new(2.0, -1.5, -6.0), Vector3::new(0.0, 1.5, -3.0)];
+    let render = |style: LineStyle| {
+        let mut r = setup();
+        r.set_line_style(style);
+        fill(&mut r, &slanted, Vector3::new(255.0, 0.0, 0.0), Primitive::ShadedWireframe);
+        r.resolve();
+        r
+    };
+    let biased = green(&render(LineStyle::default()), 0, SIZE);
+    let unbiased = green(&render(LineStyle { depth_bias: 0.0, slope_bias: 0.0, ..Default::default() }), 0, SIZE);
+    assert!(biased as f64 > unbiased as f64 * 1.2, "{} {}", biased, unbiased);
+
+    // the bias does not pull the edges through something that is really in front
+    let mut r = setup();
+    fill(&mut r, &[Vector3::new(0.0, -10.0, -1.9), Vector3::new(20.0, -10.0, -1.9), Vector3::new(0.0, 10.0, -1.9)],
+         Vector3::new(0.0, 0.0, 255.0), Primitive::Triangle);
+    fill(&mut r, &[Vector3::new(-2.0, -1.5, -2.0), Vector3::new(2.0, -1.5, -2.0), Vector3::new(0.0, 1.5, -2.0)],
+         Vector3::new(255.0, 0.0, 0.0), Primitive::ShadedWireframe);
+    r.resolve();
+    assert!(green(&r, 0, SIZE / 2 - 20) > 20);
+    assert_eq!(green(&r, SIZE / 2 + 10, SIZE), 0);
+}